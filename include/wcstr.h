@@ -0,0 +1,58 @@
+/* C interface to the wcstr crate (built with the "capi" feature).
+ *
+ * Ownership rules:
+ *  - Every WCString* returned by a wcstring_* function is owned by the caller and must be
+ *    released with exactly one call to wcstring_free().
+ *  - Pointers returned by wcstring_data() are borrowed from the WCString* and stay valid until
+ *    it is modified or freed. Never pass them to free() or wcstring_free().
+ *  - Input strings are always copied; the caller keeps ownership of them.
+ */
+
+#ifndef WCSTR_H
+#define WCSTR_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* An owned, nul-terminated UTF-16 string. Opaque. */
+typedef struct WCString WCString;
+
+/* Create a new, empty string. */
+WCString *wcstring_new(void);
+
+/* Create a string by copying the nul-terminated string at ptr.
+ * Returns NULL if ptr is NULL. */
+WCString *wcstring_from_ptr(const uint16_t *ptr);
+
+/* Create a string by copying len units (not including any terminator) from ptr.
+ * Returns NULL if ptr is NULL (and len is not 0), or if the copied units contain a nul. */
+WCString *wcstring_from_ptr_len(const uint16_t *ptr, size_t len);
+
+/* Create a copy of s. Returns NULL if s is NULL. */
+WCString *wcstring_clone(const WCString *s);
+
+/* Release a string returned by any wcstring_* function. Does nothing if s is NULL. */
+void wcstring_free(WCString *s);
+
+/* Return a pointer to the nul-terminated contents of s, or NULL if s is NULL.
+ * The pointer is borrowed: it stays valid until s is modified or freed. */
+const uint16_t *wcstring_data(const WCString *s);
+
+/* Return the length of s in uint16_t units, not including the nul terminator.
+ * Returns 0 if s is NULL. */
+size_t wcstring_len(const WCString *s);
+
+/* Append the nul-terminated string at ptr to s. ptr may point into s, e.g. to append s to itself.
+ * Returns false (leaving s unchanged) if either pointer is NULL. */
+bool wcstring_push(WCString *s, const uint16_t *ptr);
+
+#ifdef __cplusplus
+}  /* extern "C" */
+#endif
+
+#endif  /* WCSTR_H */
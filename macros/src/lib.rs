@@ -0,0 +1,35 @@
+#![deny(missing_docs,
+        missing_debug_implementations,
+        missing_copy_implementations,
+        trivial_casts,
+        trivial_numeric_casts,
+        unstable_features,
+        unused_import_braces,
+        unused_qualifications)]
+
+//! Procedural macros for the ```wcstr``` crate. Use them through ```wcstr``` with the ```wcstr-macros```
+//! feature enabled.
+
+extern crate proc_macro;
+extern crate syn;
+
+use proc_macro::TokenStream;
+
+/// Expand a string literal to a ```&'static WCStr```, encoded to UTF-16 with a ```nul``` terminator at
+/// compile time. A ```nul``` in the literal is a compile time error.
+#[proc_macro]
+pub fn wcstr(input: TokenStream) -> TokenStream {
+    let lit = match syn::parse::<syn::LitStr>(input) {
+        Ok(lit) => lit,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let value = lit.value();
+    if value.contains('\0') {
+        return syn::Error::new(lit.span(), "wcstr!: nul in string literal").to_compile_error().into();
+    }
+    let units: Vec<String> = value.encode_utf16().chain(Some(0)).map(|w| w.to_string()).collect();
+    let expanded = format!(
+        "{{ const UNITS: &'static [u16] = &[{}]; unsafe {{ ::wcstr::__private::from_units_with_nul(UNITS) }} }}",
+        units.join(", "));
+    expanded.parse().unwrap()
+}
@@ -0,0 +1,12 @@
+
+/// How ```to_ansi()``` converts characters that don't exist in the code page.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AnsiPolicy {
+    /// Use a similar looking character ("best fit"), such as ```A``` for ```\u{100}``` with Win32, or else the
+    /// default character of the code page.
+    BestFit,
+    /// Replace with the given byte, such as ```b'?'```, without best fit mapping.
+    Replace(u8),
+    /// Fail with ```io::ErrorKind::InvalidData```.
+    Strict,
+}
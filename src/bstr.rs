@@ -0,0 +1,262 @@
+
+use ::std;
+use ::std::borrow::Borrow;
+use ::std::ops::Deref;
+
+use ::{WCStr, WCString, NulError};
+use ::error;
+use ::wcstr;
+
+#[allow(non_snake_case)]
+#[link(name = "oleaut32")]
+extern "system" {
+    fn SysAllocStringLen(strIn: *const u16, ui: u32) -> *mut u16;
+    fn SysFreeString(bstrString: *mut u16);
+    fn SysStringLen(pbstr: *const u16) -> u32;
+}
+
+/// Representation of a borrowed COM automation ```BSTR```: a length prefixed "wide" string that is also
+/// ```nul``` terminated, but may contain embedded ```nul```s.
+///
+/// ```&BStr``` values are only created from a ```BString``` or an existing ```BSTR``` pointer, so
+/// ```as_ptr()``` is always a valid ```BSTR```, or null for an empty string created from a null ```BSTR```.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BStr {
+    inner: [u16],
+}
+
+/// Representation of an owned ```BSTR```, allocated with ```SysAllocStringLen``` and freed with
+/// ```SysFreeString```.
+///
+/// # ```BString``` example
+///     use wcstr::{BString, WCString};
+///     let s = WCString::from_str("Excel.Application").unwrap();
+///     let b = BString::from(s.as_wcstr());
+///     assert!(b.len() == 17);
+///     assert!(b.as_slice() == s.as_slice());
+///     assert!(b.to_wcstring().unwrap() == s);
+///     let b = BString::from_slice(&[b'a' as u16, 0, b'b' as u16]);
+///     assert!(b.len() == 3);
+///     assert!(b.to_wcstring().unwrap_err().nul_position() == 1);
+///     let b = unsafe { BString::from_raw(std::ptr::null_mut()) };
+///     assert!(b.is_empty() && b.as_ptr().is_null());
+pub struct BString {
+    ptr: *mut u16,
+}
+
+/// Stands in for a null ```BSTR```, which is a valid empty string.
+static EMPTY: [u16; 1] = [0];
+
+unsafe impl Send for BString {}
+unsafe impl Sync for BString {}
+
+impl BStr {
+    /// Create a ```&BStr``` from a ```BSTR```, using ```SysStringLen``` for its length.
+    ///
+    /// * ```p``` must be null or a valid ```BSTR``` that outlives ```'a```. A null ```BSTR``` is an empty
+    ///   string, and ```as_ptr()``` returns null for it.
+    pub unsafe fn from_ptr<'a>(p: *const u16) -> &'a BStr {
+        if p.is_null() {
+            return std::mem::transmute::<&[u16], &BStr>(&EMPTY[..]);
+        }
+        let len = SysStringLen(p) as usize;
+        std::mem::transmute(std::slice::from_raw_parts(p, len + 1))
+    }
+
+    /// Return the length in ```u16```s, not including the ```nul``` terminator.
+    pub fn len(&self) -> usize {
+        self.inner.len() - 1
+    }
+
+    /// is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the ```BSTR``` pointer, which is null if the ```&BStr``` was created from a null ```BSTR```.
+    pub fn as_ptr(&self) -> *const u16 {
+        if std::ptr::eq(self.inner.as_ptr(), EMPTY.as_ptr()) {
+            return std::ptr::null();
+        }
+        self.inner.as_ptr()
+    }
+
+    /// Return the string as a slice of ```u16```s, without the ```nul``` terminator.
+    pub fn as_slice(&self) -> &[u16] {
+        &self.inner[..self.len()]
+    }
+
+    /// Return the string as a slice of ```u16```s, including the ```nul``` terminator.
+    pub fn as_slice_with_nul(&self) -> &[u16] {
+        &self.inner
+    }
+
+    /// Return the string as a ```&WCStr```, or ```NulError``` if it contains an embedded ```nul```.
+    pub fn as_wcstr(&self) -> Result<&WCStr, NulError> {
+        match self.as_slice().iter().position(|&w| w == 0) {
+            Some(i) => Err(error::nul(i, None)),
+            None => Ok(unsafe { wcstr::from_units_with_nul(&self.inner) }),
+        }
+    }
+
+    /// Copy the string to a ```WCString```, or return ```NulError``` if it contains an embedded ```nul```.
+    pub fn to_wcstring(&self) -> Result<WCString, NulError> {
+        self.as_wcstr().map(|s| s.to_owned())
+    }
+
+    /// Decode the string to a ```String```, replacing invalid UTF-16 with ```U+FFFD REPLACEMENT CHARACTER```.
+    pub fn to_string_lossy(&self) -> String {
+        String::from_utf16_lossy(self.as_slice())
+    }
+}
+
+impl std::fmt::Debug for BStr {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        std::fmt::Debug::fmt(&self.to_string_lossy(), f)
+    }
+}
+
+impl ToOwned for BStr {
+    type Owned = BString;
+    fn to_owned(&self) -> BString {
+        BString::from_slice(self.as_slice())
+    }
+}
+
+impl BString {
+    /// Create a new, empty ```BSTR```.
+    pub fn new() -> BString {
+        BString::from_slice(&[])
+    }
+
+    /// Create a ```BSTR``` by copying a slice of ```u16```s, which may contain ```nul```s.
+    ///
+    /// * This will panic if ```SysAllocStringLen``` fails.
+    pub fn from_slice(slice: &[u16]) -> BString {
+        assert!(slice.len() <= u32::MAX as usize / 2, "string too long for a BSTR");
+        let ptr = unsafe { SysAllocStringLen(slice.as_ptr(), slice.len() as u32) };
+        assert!(!ptr.is_null(), "SysAllocStringLen failed");
+        BString {
+            ptr: ptr,
+        }
+    }
+
+    /// Take ownership of a ```BSTR```, which will be freed with ```SysFreeString```.
+    ///
+    /// * ```p``` must be null (an empty string, as returned by COM ```[out]``` parameters) or a valid ```BSTR```
+    ///   that is not owned elsewhere.
+    pub unsafe fn from_raw(p: *mut u16) -> BString {
+        BString {
+            ptr: p,
+        }
+    }
+
+    /// Release ownership of the ```BSTR```, which must then be freed with ```SysFreeString```, typically by
+    /// passing it to a COM method as an ```[out]``` parameter.
+    pub fn into_raw(self) -> *mut u16 {
+        let ptr = self.ptr;
+        std::mem::forget(self);
+        ptr
+    }
+
+    /// Return the ```BSTR``` as a ```&BStr```.
+    pub fn as_bstr(&self) -> &BStr {
+        unsafe { BStr::from_ptr(self.ptr) }
+    }
+}
+
+impl Drop for BString {
+    fn drop(&mut self) {
+        unsafe { SysFreeString(self.ptr) };
+    }
+}
+
+impl Deref for BString {
+    type Target = BStr;
+    fn deref(&self) -> &BStr {
+        self.as_bstr()
+    }
+}
+
+impl Borrow<BStr> for BString {
+    fn borrow(&self) -> &BStr {
+        self.as_bstr()
+    }
+}
+
+impl AsRef<BStr> for BString {
+    fn as_ref(&self) -> &BStr {
+        self.as_bstr()
+    }
+}
+
+impl Clone for BString {
+    fn clone(&self) -> BString {
+        self.as_bstr().to_owned()
+    }
+}
+
+impl Default for BString {
+    fn default() -> BString {
+        BString::new()
+    }
+}
+
+impl PartialEq for BString {
+    fn eq(&self, other: &BString) -> bool {
+        self.as_bstr() == other.as_bstr()
+    }
+}
+
+impl Eq for BString {}
+
+impl std::hash::Hash for BString {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.as_bstr().hash(state)
+    }
+}
+
+impl std::fmt::Debug for BString {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self.as_bstr(), f)
+    }
+}
+
+impl<'a> From<&'a WCStr> for BString {
+    fn from(s: &'a WCStr) -> BString {
+        BString::from_slice(s.to_slice())
+    }
+}
+
+impl From<WCString> for BString {
+    fn from(s: WCString) -> BString {
+        BString::from_slice(s.as_slice())
+    }
+}
+
+impl<'a> From<&'a WCString> for BString {
+    fn from(s: &'a WCString) -> BString {
+        BString::from_slice(s.as_slice())
+    }
+}
+
+impl<'a> std::convert::TryFrom<&'a BStr> for WCString {
+    type Error = NulError;
+    fn try_from(s: &'a BStr) -> Result<WCString, NulError> {
+        s.to_wcstring()
+    }
+}
+
+impl std::convert::TryFrom<BString> for WCString {
+    type Error = NulError;
+    fn try_from(s: BString) -> Result<WCString, NulError> {
+        s.to_wcstring()
+    }
+}
+
+impl WCStr {
+    /// Copy this "wide" string to a new ```BSTR```, see ```BString```.
+    pub fn to_bstring(&self) -> BString {
+        BString::from(self)
+    }
+}
@@ -0,0 +1,141 @@
+
+use ::std;
+use ::std::io;
+
+use ::WCString;
+
+/// ```MAX_PATH```, the initial buffer size of ```WCString::from_win32()```.
+const INITIAL_CAPACITY: u32 = 260;
+/// ```ERROR_INSUFFICIENT_BUFFER```
+const ERROR_INSUFFICIENT_BUFFER: i32 = 122;
+
+/// A zero-filled output buffer for Win32 functions that write a "wide" string into caller-provided
+/// storage, such as ```GetWindowTextW``` or ```GetModuleFileNameW```.
+///
+/// Pass ```as_mut_ptr()``` and ```capacity()``` to the function, then either call ```set_len()``` with the
+/// number of ```u16```s it reports writing, or let ```into_wcstring()``` scan for the ```nul``` terminator.
+///
+/// # ```WCBuffer``` example
+///     use wcstr::WCBuffer;
+///     # unsafe fn GetWindowTextW(_: usize, p: *mut u16, n: i32) -> i32 {
+///     #     let text: Vec<u16> = "Untitled - Notepad".encode_utf16().collect();
+///     #     std::ptr::copy_nonoverlapping(text.as_ptr(), p, text.len().min(n as usize - 1));
+///     #     text.len().min(n as usize - 1) as i32
+///     # }
+///     let mut buffer = WCBuffer::new(256);
+///     let len = unsafe { GetWindowTextW(0, buffer.as_mut_ptr(), buffer.capacity() as i32) };
+///     unsafe { buffer.set_len(len as usize) };
+///     assert!(buffer.into_wcstring() == "Untitled - Notepad");
+#[derive(Clone, Debug)]
+pub struct WCBuffer {
+    inner: Vec<u16>,
+    len: Option<usize>,
+}
+
+impl WCBuffer {
+    /// Create a zero-filled buffer of ```capacity``` ```u16```s, including room for the ```nul``` terminator.
+    ///
+    /// * This will assert if ```capacity``` is 0.
+    pub fn new(capacity: usize) -> WCBuffer {
+        assert!(capacity > 0);
+        WCBuffer {
+            inner: vec![0; capacity],
+            len: None,
+        }
+    }
+
+    /// Return the size of the buffer in ```u16```s, including room for the ```nul``` terminator.
+    pub fn capacity(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Return a pointer to the buffer, valid for writes of ```capacity()``` ```u16```s.
+    pub fn as_mut_ptr(&mut self) -> *mut u16 {
+        self.inner.as_mut_ptr()
+    }
+
+    /// Return the buffer as a mutable slice of ```capacity()``` ```u16```s.
+    pub fn as_mut_slice(&mut self) -> &mut [u16] {
+        &mut self.inner
+    }
+
+    /// Set the length of the string written into the buffer, not counting the ```nul``` terminator.
+    ///
+    /// The first ```len``` ```u16```s must not contain a ```nul```. They are trusted without scanning.
+    ///
+    /// * This will assert if ```len``` is not less than ```capacity()```.
+    pub unsafe fn set_len(&mut self, len: usize) {
+        assert!(len < self.inner.len());
+        self.len = Some(len);
+    }
+
+    /// Convert the buffer into a ```WCString```, dropping the unused part of the buffer.
+    ///
+    /// * If ```set_len()``` was called, the string is the first ```len``` ```u16```s.
+    /// * Otherwise the buffer is scanned for the ```nul``` terminator. If there is none, the whole buffer
+    ///   is used.
+    pub fn into_wcstring(self) -> WCString {
+        let mut v = self.inner;
+        let len = match self.len {
+            Some(len) => len,
+            None => v.iter().position(|&w| w == 0).unwrap_or(v.len()),
+        };
+        v.truncate(len);
+        v.shrink_to_fit();
+        unsafe { WCString::from_vec_unchecked(v) }
+    }
+}
+
+impl WCString {
+    /// Call a Win32 function that writes a string into a caller-provided buffer, growing the buffer and
+    /// retrying until the string fits.
+    ///
+    /// ```f``` is called with a buffer and its capacity in ```u16```s (including room for the ```nul```
+    /// terminator), and returns:
+    ///
+    /// * ```Ok(len)``` with ```len < capacity```: the string fit, and ```len``` ```u16```s were written, not
+    ///   counting the ```nul```.
+    /// * ```Ok(len)``` with ```len >= capacity```: the buffer was too small. Functions like
+    ///   ```GetCurrentDirectoryW``` return the required size, and functions like ```GetModuleFileNameW```
+    ///   return the capacity when they truncate; either way the buffer grows and ```f``` is called again.
+    /// * ```Err(e)```: the call failed. ```ERROR_INSUFFICIENT_BUFFER``` grows the buffer and retries, and any
+    ///   other error is returned.
+    ///
+    /// # ```from_win32()``` example
+    ///     use std::io;
+    ///     use wcstr::WCString;
+    ///     # unsafe fn GetCurrentDirectoryW(n: u32, p: *mut u16) -> u32 {
+    ///     #     let dir: Vec<u16> = "C:\\".encode_utf16().chain(std::iter::repeat(b'x' as u16).take(300)).collect();
+    ///     #     if (n as usize) <= dir.len() { return dir.len() as u32 + 1; }
+    ///     #     std::ptr::copy_nonoverlapping(dir.as_ptr(), p, dir.len());
+    ///     #     *p.add(dir.len()) = 0;
+    ///     #     dir.len() as u32
+    ///     # }
+    ///     let dir = WCString::from_win32(|buf, cap| {
+    ///         match unsafe { GetCurrentDirectoryW(cap, buf) } {
+    ///             0 => Err(io::Error::last_os_error()),
+    ///             len => Ok(len),
+    ///         }
+    ///     }).unwrap();
+    ///     assert!(dir.len() == 303);
+    pub fn from_win32<F>(mut f: F) -> io::Result<WCString>
+        where F: FnMut(*mut u16, u32) -> io::Result<u32> {
+        let mut capacity = INITIAL_CAPACITY;
+        loop {
+            let mut buffer = WCBuffer::new(capacity as usize);
+            let required = match f(buffer.as_mut_ptr(), capacity) {
+                Ok(len) if len < capacity => {
+                    unsafe { buffer.set_len(len as usize) };
+                    return Ok(buffer.into_wcstring());
+                },
+                Ok(len) => len,
+                Err(ref e) if e.raw_os_error() == Some(ERROR_INSUFFICIENT_BUFFER) => 0,
+                Err(e) => return Err(e),
+            };
+            capacity = match capacity.checked_mul(2) {
+                Some(doubled) => std::cmp::max(doubled, required),
+                None => return Err(io::Error::other("WCString::from_win32: string too long")),
+            };
+        }
+    }
+}
@@ -0,0 +1,111 @@
+
+use ::std;
+
+use ::{WCStr, WCString, FromBytesError};
+use ::error;
+
+const BOM: u16 = 0xFEFF;
+
+// Decode pairs of bytes into u16s, dropping a final nul terminator and rejecting any other nul.
+fn decode<F>(bytes: &[u8], f: F) -> Result<WCString, FromBytesError>
+    where F: Fn([u8; 2]) -> u16 {
+    if !bytes.len().is_multiple_of(2) {
+        return Err(error::from_bytes(bytes.len(), None));
+    }
+    let mut v: Vec<u16> = bytes.chunks(2).map(|b| f([b[0], b[1]])).collect();
+    if v.last() == Some(&0) {
+        v.pop();
+    }
+    match v.iter().position(|&w| w == 0) {
+        Some(i) => Err(error::from_bytes(bytes.len(), Some(i))),
+        None => Ok(unsafe { WCString::from_vec_unchecked(v) }),
+    }
+}
+
+impl WCStr {
+    /// Return the UTF-16LE bytes of this "wide" string, without the ```nul``` terminator and without copying.
+    /// Only available on little-endian targets, where that is the in-memory representation.
+    ///
+    /// # ```as_bytes_le()``` example
+    ///     use wcstr::WCString;
+    ///     let s = WCString::from_str("Hi").unwrap();
+    ///     assert!(s.as_bytes_le() == [b'H', 0, b'i', 0]);
+    ///     assert!(s.as_bytes_with_nul_le() == [b'H', 0, b'i', 0, 0, 0]);
+    #[cfg(target_endian = "little")]
+    pub fn as_bytes_le(&self) -> &[u8] {
+        let bytes = self.as_bytes_with_nul_le();
+        &bytes[..bytes.len() - 2]
+    }
+
+    /// Return the UTF-16LE bytes of this "wide" string, including the ```nul``` terminator, without copying.
+    /// Only available on little-endian targets.
+    #[cfg(target_endian = "little")]
+    pub fn as_bytes_with_nul_le(&self) -> &[u8] {
+        let s = self.to_slice_with_nul();
+        unsafe { std::slice::from_raw_parts(s.as_ptr() as *const u8, s.len() * 2) }
+    }
+
+    /// Return the UTF-16BE bytes of this "wide" string, without a byte order mark or ```nul``` terminator.
+    ///
+    /// # ```to_utf16be_bytes()``` example
+    ///     use wcstr::WCString;
+    ///     let s = WCString::from_str("Hi").unwrap();
+    ///     assert!(s.to_utf16be_bytes() == [0, b'H', 0, b'i']);
+    pub fn to_utf16be_bytes(&self) -> Vec<u8> {
+        let mut v = Vec::with_capacity(self.len() * 2);
+        for &w in self.to_slice() {
+            v.extend_from_slice(&w.to_be_bytes());
+        }
+        v
+    }
+}
+
+impl WCString {
+    /// Create a ```WCString``` from UTF-16LE bytes.
+    ///
+    /// * The length must be even.
+    /// * A ```nul``` terminator at the end is allowed and dropped; any other ```nul``` is an error.
+    ///
+    /// # ```from_bytes_le()``` example
+    ///     use wcstr::WCString;
+    ///     let s = WCString::from_bytes_le(&[b'H', 0, b'i', 0, 0, 0]).unwrap();
+    ///     assert!(s == "Hi");
+    ///     assert!(WCString::from_bytes_le(&[b'H', 0, b'i']).unwrap_err().nul_position() == None);
+    ///     assert!(WCString::from_bytes_le(&[0, 0, b'i', 0]).unwrap_err().nul_position() == Some(0));
+    pub fn from_bytes_le<T>(bytes: T) -> Result<WCString, FromBytesError>
+        where T: AsRef<[u8]> {
+        decode(bytes.as_ref(), u16::from_le_bytes)
+    }
+
+    /// Create a ```WCString``` from UTF-16BE bytes, with the same rules as ```from_bytes_le()```.
+    pub fn from_bytes_be<T>(bytes: T) -> Result<WCString, FromBytesError>
+        where T: AsRef<[u8]> {
+        decode(bytes.as_ref(), u16::from_be_bytes)
+    }
+
+    /// Create a ```WCString``` from UTF-16 bytes, using the byte order mark to pick the byte order.
+    ///
+    /// * ```FF FE``` is UTF-16LE and ```FE FF``` is UTF-16BE. The byte order mark is dropped.
+    /// * Without a byte order mark, the bytes are read as UTF-16LE, like Windows does.
+    /// * Otherwise the same rules as ```from_bytes_le()``` apply.
+    ///
+    /// # ```from_utf16_bytes()``` example
+    ///     use wcstr::WCString;
+    ///     let be = WCString::from_utf16_bytes(&[0xFE, 0xFF, 0, b'H', 0, b'i']).unwrap();
+    ///     let le = WCString::from_utf16_bytes(&[0xFF, 0xFE, b'H', 0, b'i', 0]).unwrap();
+    ///     let none = WCString::from_utf16_bytes(&[b'H', 0, b'i', 0]).unwrap();
+    ///     assert!(be == "Hi" && le == "Hi" && none == "Hi");
+    pub fn from_utf16_bytes<T>(bytes: T) -> Result<WCString, FromBytesError>
+        where T: AsRef<[u8]> {
+        let bytes = bytes.as_ref();
+        if bytes.starts_with(&BOM.to_be_bytes()) {
+            WCString::from_bytes_be(&bytes[2..])
+        }
+        else if bytes.starts_with(&BOM.to_le_bytes()) {
+            WCString::from_bytes_le(&bytes[2..])
+        }
+        else {
+            WCString::from_bytes_le(bytes)
+        }
+    }
+}
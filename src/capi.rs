@@ -0,0 +1,142 @@
+
+//! A C ABI for creating and consuming ```WCString```s from C and C++.
+//!
+//! The matching header is ```include/wcstr.h```. It is written by hand, so keep it in sync with this module.
+//!
+//! Ownership rules:
+//!
+//! * Every ```WCString*``` returned by a ```wcstring_*``` function is owned by the caller and must be
+//!   released with exactly one call to ```wcstring_free()```.
+//! * Pointers returned by ```wcstring_data()``` are borrowed from the ```WCString*``` and stay valid
+//!   until it is modified or freed. They must never be passed to ```free()``` or ```wcstring_free()```.
+//! * Input strings are always copied; the caller keeps ownership of them.
+//!
+//! # ```capi``` example
+//!     use std::ptr;
+//!     use wcstr::capi::*;
+//!     unsafe {
+//!         let s = wcstring_new();
+//!         assert!(wcstring_len(s) == 0);
+//!         let hello: Vec<u16> = "hello\0".encode_utf16().collect();
+//!         assert!(wcstring_push(s, hello.as_ptr()));
+//!         assert!(wcstring_len(s) == 5);
+//!         let data = wcstring_data(s);
+//!         assert!(*data.add(5) == 0);
+//!         let copy = wcstring_from_ptr(data);
+//!         assert!(wcstring_len(copy) == 5);
+//!         wcstring_free(copy);
+//!         // Units with an embedded nul are rejected.
+//!         let units = [b'a' as u16, 0, b'b' as u16];
+//!         assert!(wcstring_from_ptr_len(units.as_ptr(), 3).is_null());
+//!         let a = wcstring_from_ptr_len(units.as_ptr(), 1);
+//!         assert!(wcstring_len(a) == 1);
+//!         wcstring_free(a);
+//!         // NULL is handled by every function.
+//!         assert!(wcstring_from_ptr(ptr::null()).is_null());
+//!         assert!(wcstring_from_ptr_len(ptr::null(), 1).is_null());
+//!         assert!(wcstring_clone(ptr::null()).is_null());
+//!         assert!(wcstring_data(ptr::null()).is_null());
+//!         assert!(wcstring_len(ptr::null()) == 0);
+//!         assert!(!wcstring_push(s, ptr::null()));
+//!         assert!(!wcstring_push(ptr::null_mut(), hello.as_ptr()));
+//!         assert!(wcstring_len(s) == 5);
+//!         // Appending a string to itself is fine, since the input is copied first.
+//!         assert!(wcstring_push(s, wcstring_data(s)));
+//!         assert!(wcstring_len(s) == 10);
+//!         assert!(*s == "hellohello");
+//!         wcstring_free(ptr::null_mut());
+//!         wcstring_free(s);
+//!     }
+
+use ::std;
+
+use ::{WCStr, WCString};
+
+fn into_handle(s: WCString) -> *mut WCString {
+    Box::into_raw(Box::new(s))
+}
+
+/// Create a new, empty string.
+#[no_mangle]
+pub extern "C" fn wcstring_new() -> *mut WCString {
+    into_handle(WCString::new())
+}
+
+/// Create a string by copying the nul-terminated string at ```ptr```.
+/// Returns ```NULL``` if ```ptr``` is ```NULL```.
+#[no_mangle]
+pub unsafe extern "C" fn wcstring_from_ptr(ptr: *const u16) -> *mut WCString {
+    if ptr.is_null() {
+        return std::ptr::null_mut();
+    }
+    into_handle(WCStr::from_ptr(ptr).to_owned())
+}
+
+/// Create a string by copying ```len``` ```u16```s (not including any terminator) from ```ptr```.
+/// Returns ```NULL``` if ```ptr``` is ```NULL``` (and ```len``` is not 0), or if the copied units contain a nul.
+#[no_mangle]
+pub unsafe extern "C" fn wcstring_from_ptr_len(ptr: *const u16, len: usize) -> *mut WCString {
+    if len == 0 {
+        return wcstring_new();
+    }
+    if ptr.is_null() {
+        return std::ptr::null_mut();
+    }
+    match WCString::from_vec(std::slice::from_raw_parts(ptr, len)) {
+        Ok(s) => into_handle(s),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/// Create a copy of ```s```. Returns ```NULL``` if ```s``` is ```NULL```.
+#[no_mangle]
+pub unsafe extern "C" fn wcstring_clone(s: *const WCString) -> *mut WCString {
+    match s.as_ref() {
+        Some(s) => into_handle(s.clone()),
+        None => std::ptr::null_mut(),
+    }
+}
+
+/// Release a string returned by any ```wcstring_*``` function. Does nothing if ```s``` is ```NULL```.
+#[no_mangle]
+pub unsafe extern "C" fn wcstring_free(s: *mut WCString) {
+    if !s.is_null() {
+        drop(Box::from_raw(s));
+    }
+}
+
+/// Return a pointer to the nul-terminated contents of ```s```, or ```NULL``` if ```s``` is ```NULL```.
+/// The pointer is borrowed: it stays valid until ```s``` is modified or freed.
+#[no_mangle]
+pub unsafe extern "C" fn wcstring_data(s: *const WCString) -> *const u16 {
+    match s.as_ref() {
+        Some(s) => s.as_ptr(),
+        None => std::ptr::null(),
+    }
+}
+
+/// Return the length of ```s``` in ```u16``` units, not including the nul terminator.
+/// Returns 0 if ```s``` is ```NULL```.
+#[no_mangle]
+pub unsafe extern "C" fn wcstring_len(s: *const WCString) -> usize {
+    match s.as_ref() {
+        Some(s) => s.len(),
+        None => 0,
+    }
+}
+
+/// Append the nul-terminated string at ```ptr``` to ```s```. ```ptr``` may point into ```s```, e.g. to append
+/// ```s``` to itself.
+/// Returns ```false``` (leaving ```s``` unchanged) if either pointer is ```NULL```.
+#[no_mangle]
+pub unsafe extern "C" fn wcstring_push(s: *mut WCString, ptr: *const u16) -> bool {
+    match s.as_mut() {
+        Some(s) if !ptr.is_null() => {
+            // Copy first: ptr may point into s itself, whose buffer push() can reallocate.
+            let tail = WCStr::from_ptr(ptr).to_owned();
+            s.push(&tail);
+            true
+        },
+        _ => false,
+    }
+}
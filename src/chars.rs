@@ -0,0 +1,170 @@
+
+use ::std;
+use ::utf16;
+
+/// Created with method ```.chars()```
+///
+/// Yields ```Ok(char)``` for every character and ```Err(u16)``` for every unpaired surrogate.
+#[derive(Clone, Debug)]
+pub struct Chars<'a> {
+    inner: &'a [u16],
+}
+
+/// Created with method ```.chars_lossy()```
+///
+/// Yields every character, replacing unpaired surrogates with ```U+FFFD REPLACEMENT CHARACTER```.
+#[derive(Clone, Debug)]
+pub struct CharsLossy<'a> {
+    inner: Chars<'a>,
+}
+
+/// Created with method ```.char_indices()```
+///
+/// Yields the ```u16``` offset of every character along with ```Ok(char)```, or ```Err(u16)``` for an
+/// unpaired surrogate.
+#[derive(Clone, Debug)]
+pub struct CharIndices<'a> {
+    inner: Chars<'a>,
+    offset: usize,
+}
+
+/// Created with method ```.code_units()```, or by iterating over a ```&WCStr```
+///
+/// Yields every ```u16``` of the string, without the ```nul``` terminator.
+#[derive(Clone, Debug)]
+pub struct CodeUnits<'a> {
+    inner: std::slice::Iter<'a, u16>,
+}
+
+pub fn new_code_units<'a>(s: &'a [u16]) -> CodeUnits<'a> {
+    CodeUnits {
+        inner: s.iter(),
+    }
+}
+
+pub fn new<'a>(s: &'a [u16]) -> Chars<'a> {
+    Chars {
+        inner: s,
+    }
+}
+
+pub fn new_lossy<'a>(s: &'a [u16]) -> CharsLossy<'a> {
+    CharsLossy {
+        inner: new(s),
+    }
+}
+
+pub fn new_indices<'a>(s: &'a [u16]) -> CharIndices<'a> {
+    CharIndices {
+        inner: new(s),
+        offset: 0,
+    }
+}
+
+fn decode_unit(w: u16) -> Result<char, u16> {
+    std::char::from_u32(w as u32).ok_or(w)
+}
+
+impl<'a> Iterator for Chars<'a> {
+    type Item = Result<char, u16>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let (&w, rest) = self.inner.split_first()?;
+        if utf16::is_high_surrogate(w) {
+            if let Some(&l) = rest.first() {
+                if utf16::is_low_surrogate(l) {
+                    self.inner = &rest[1..];
+                    return Some(Ok(utf16::decode_pair(w, l)));
+                }
+            }
+        }
+        self.inner = rest;
+        Some(decode_unit(w))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.inner.len();
+        (len.div_ceil(2), Some(len))
+    }
+}
+
+impl<'a> DoubleEndedIterator for Chars<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (&w, rest) = self.inner.split_last()?;
+        if utf16::is_low_surrogate(w) {
+            if let Some(&h) = rest.last() {
+                if utf16::is_high_surrogate(h) {
+                    self.inner = &rest[..rest.len() - 1];
+                    return Some(Ok(utf16::decode_pair(h, w)));
+                }
+            }
+        }
+        self.inner = rest;
+        Some(decode_unit(w))
+    }
+}
+
+impl<'a> Iterator for CharsLossy<'a> {
+    type Item = char;
+    fn next(&mut self) -> Option<char> {
+        self.inner.next().map(|c| c.unwrap_or(std::char::REPLACEMENT_CHARACTER))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for CharsLossy<'a> {
+    fn next_back(&mut self) -> Option<char> {
+        self.inner.next_back().map(|c| c.unwrap_or(std::char::REPLACEMENT_CHARACTER))
+    }
+}
+
+impl<'a> std::iter::FusedIterator for Chars<'a> {}
+
+impl<'a> std::iter::FusedIterator for CharsLossy<'a> {}
+
+impl<'a> Iterator for CharIndices<'a> {
+    type Item = (usize, Result<char, u16>);
+    fn next(&mut self) -> Option<Self::Item> {
+        let len = self.inner.inner.len();
+        self.inner.next().map(|c| {
+            let offset = self.offset;
+            self.offset += len - self.inner.inner.len();
+            (offset, c)
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for CharIndices<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|c| (self.offset + self.inner.inner.len(), c))
+    }
+}
+
+impl<'a> std::iter::FusedIterator for CharIndices<'a> {}
+
+impl<'a> Iterator for CodeUnits<'a> {
+    type Item = u16;
+    fn next(&mut self) -> Option<u16> {
+        self.inner.next().cloned()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for CodeUnits<'a> {
+    fn next_back(&mut self) -> Option<u16> {
+        self.inner.next_back().cloned()
+    }
+}
+
+impl<'a> ExactSizeIterator for CodeUnits<'a> {}
+
+impl<'a> std::iter::FusedIterator for CodeUnits<'a> {}
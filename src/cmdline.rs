@@ -0,0 +1,103 @@
+
+use ::std;
+use ::{WCStr, WCString, WCStrCursor};
+use ::path::BACKSLASH;
+
+const SPACE: u16 = b' ' as u16;
+const TAB: u16 = b'\t' as u16;
+const QUOTE: u16 = b'"' as u16;
+
+/// Created with method ```.command_line_args()```
+///
+/// Yields every argument of a command line, split with the rules of ```CommandLineToArgvW```.
+#[derive(Clone, Debug)]
+pub struct CommandLineArgs<'a> {
+    cursor: WCStrCursor<'a>,
+    first: bool,
+}
+
+pub fn new<'a>(s: &'a WCStr) -> CommandLineArgs<'a> {
+    CommandLineArgs {
+        cursor: WCStrCursor::new(s),
+        first: true,
+    }
+}
+
+fn is_blank(w: u16) -> bool {
+    w == SPACE || w == TAB
+}
+
+impl<'a> CommandLineArgs<'a> {
+    // The program name has its own rules: it ends at the next quote if it starts with one, otherwise at the
+    // first space or tab. Backslashes are never escapes in it.
+    fn program_name(&mut self) -> Vec<u16> {
+        let cursor = &mut self.cursor;
+        if cursor.eat(QUOTE) {
+            let name = cursor.take_while(|w| w != QUOTE).to_vec();
+            cursor.eat(QUOTE);
+            name
+        }
+        else {
+            cursor.take_while(|w| !is_blank(w)).to_vec()
+        }
+    }
+
+    fn argument(&mut self) -> Vec<u16> {
+        let cursor = &mut self.cursor;
+        let mut arg = Vec::new();
+        let mut in_quotes = false;
+        while let Some(w) = cursor.peek() {
+            match w {
+                w if is_blank(w) && !in_quotes => break,
+                BACKSLASH => {
+                    let n = cursor.skip_while(|w| w == BACKSLASH);
+                    if cursor.peek() == Some(QUOTE) {
+                        arg.extend(std::iter::repeat_n(BACKSLASH, n / 2));
+                        if n % 2 == 1 {
+                            arg.push(QUOTE);
+                            cursor.advance();
+                        }
+                    }
+                    else {
+                        arg.extend(std::iter::repeat_n(BACKSLASH, n));
+                    }
+                },
+                QUOTE => {
+                    cursor.advance();
+                    if in_quotes && cursor.eat(QUOTE) {
+                        arg.push(QUOTE);
+                    }
+                    else {
+                        in_quotes = !in_quotes;
+                    }
+                },
+                w => {
+                    arg.push(w);
+                    cursor.advance();
+                },
+            }
+        }
+        arg
+    }
+}
+
+impl<'a> Iterator for CommandLineArgs<'a> {
+    type Item = WCString;
+    fn next(&mut self) -> Option<WCString> {
+        if self.first {
+            self.first = false;
+            if !self.cursor.is_eof() {
+                let name = self.program_name();
+                return Some(unsafe { WCString::from_vec_unchecked(name) });
+            }
+        }
+        self.cursor.skip_while(is_blank);
+        if self.cursor.is_eof() {
+            return None;
+        }
+        let arg = self.argument();
+        Some(unsafe { WCString::from_vec_unchecked(arg) })
+    }
+}
+
+impl<'a> std::iter::FusedIterator for CommandLineArgs<'a> {}
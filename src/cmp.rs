@@ -0,0 +1,108 @@
+
+use ::std::cmp::Ordering;
+
+use ::WCStr;
+use ::utf16;
+
+/// A way of ordering "wide" strings, used to parameterize sorted containers like ```WCStrMap```.
+pub trait WCStrOrdering {
+    /// Compare two "wide" strings.
+    fn cmp(a: &WCStr, b: &WCStr) -> Ordering;
+}
+
+/// Order by code units, exactly like ```Ord``` for ```WCStr```.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct Ordinal;
+
+/// Order by code units after ```to_upper_invariant()```, like the OS does for names in its
+/// case-insensitive namespaces (files, registry keys, kernel objects).
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct OrdinalIgnoreCase;
+
+/// "Natural" order, where runs of ASCII digits are compared by their numeric value, like Explorer sorts
+/// file names (```file2``` before ```file10```). Everything else is compared like ```OrdinalIgnoreCase```.
+///
+/// Strings that only differ in leading zeros are ordered like ```OrdinalIgnoreCase```, so they stay distinct
+/// keys.
+///
+/// # ```Natural``` example
+///     use wcstr::{Natural, WCStrSet, WCString};
+///     let mut names = WCStrSet::with_ordering(Natural);
+///     for name in &["file10", "file2", "file1", "file01", "FILE1"] {
+///         names.insert(WCString::from_str(name).unwrap());
+///     }
+///     let names: Vec<String> = names.iter().map(|s| s.to_string_lossy()).collect();
+///     assert!(names == ["file01", "file1", "file2", "file10"]);
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct Natural;
+
+impl WCStrOrdering for Ordinal {
+    fn cmp(a: &WCStr, b: &WCStr) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+impl WCStrOrdering for OrdinalIgnoreCase {
+    fn cmp(a: &WCStr, b: &WCStr) -> Ordering {
+        a.cmp_upper_invariant(b)
+    }
+}
+
+impl WCStrOrdering for Natural {
+    fn cmp(a: &WCStr, b: &WCStr) -> Ordering {
+        natural(a.to_slice(), b.to_slice())
+    }
+}
+
+fn is_digit(w: u16) -> bool {
+    w >= b'0' as u16 && w <= b'9' as u16
+}
+
+fn digit_run(s: &[u16], start: usize) -> usize {
+    let mut end = start;
+    while end < s.len() && is_digit(s[end]) {
+        end += 1;
+    }
+    end
+}
+
+fn trim_zeros(s: &[u16]) -> &[u16] {
+    match s.iter().position(|&w| w != b'0' as u16) {
+        Some(i) => &s[i..],
+        None => &s[s.len()..],
+    }
+}
+
+/// Compare two strings in "natural" order. When the strings only differ in leading zeros, they are compared
+/// by their units after ```to_upper_invariant()```, so ```a01``` comes before ```a1```.
+pub fn natural(a: &[u16], b: &[u16]) -> Ordering {
+    let mut i = 0;
+    let mut j = 0;
+    while i < a.len() && j < b.len() {
+        if is_digit(a[i]) && is_digit(b[j]) {
+            let end_a = digit_run(a, i);
+            let end_b = digit_run(b, j);
+            let da = trim_zeros(&a[i..end_a]);
+            let db = trim_zeros(&b[j..end_b]);
+            let ord = da.len().cmp(&db.len()).then(da.cmp(db));
+            if ord != Ordering::Equal {
+                return ord;
+            }
+            i = end_a;
+            j = end_b;
+        }
+        else {
+            let ord = utf16::to_upper_invariant(a[i]).cmp(&utf16::to_upper_invariant(b[j]));
+            if ord != Ordering::Equal {
+                return ord;
+            }
+            i += 1;
+            j += 1;
+        }
+    }
+    (a.len() - i).cmp(&(b.len() - j)).then_with(|| {
+        let a = a.iter().map(|&w| utf16::to_upper_invariant(w));
+        let b = b.iter().map(|&w| utf16::to_upper_invariant(w));
+        a.cmp(b)
+    })
+}
@@ -0,0 +1,157 @@
+
+use ::std::io;
+
+use ::{WCStr, WCString, AnsiPolicy};
+
+const CP_UTF8: u32 = 65001;
+
+/// The upper halves (```0x80``` to ```0xFF```) of the supported single-byte code pages, as Windows maps them.
+/// The lower halves are ASCII.
+static CP1252: [u16; 128] = [
+    0x20AC, 0x0081, 0x201A, 0x0192, 0x201E, 0x2026, 0x2020, 0x2021,
+    0x02C6, 0x2030, 0x0160, 0x2039, 0x0152, 0x008D, 0x017D, 0x008F,
+    0x0090, 0x2018, 0x2019, 0x201C, 0x201D, 0x2022, 0x2013, 0x2014,
+    0x02DC, 0x2122, 0x0161, 0x203A, 0x0153, 0x009D, 0x017E, 0x0178,
+    0x00A0, 0x00A1, 0x00A2, 0x00A3, 0x00A4, 0x00A5, 0x00A6, 0x00A7,
+    0x00A8, 0x00A9, 0x00AA, 0x00AB, 0x00AC, 0x00AD, 0x00AE, 0x00AF,
+    0x00B0, 0x00B1, 0x00B2, 0x00B3, 0x00B4, 0x00B5, 0x00B6, 0x00B7,
+    0x00B8, 0x00B9, 0x00BA, 0x00BB, 0x00BC, 0x00BD, 0x00BE, 0x00BF,
+    0x00C0, 0x00C1, 0x00C2, 0x00C3, 0x00C4, 0x00C5, 0x00C6, 0x00C7,
+    0x00C8, 0x00C9, 0x00CA, 0x00CB, 0x00CC, 0x00CD, 0x00CE, 0x00CF,
+    0x00D0, 0x00D1, 0x00D2, 0x00D3, 0x00D4, 0x00D5, 0x00D6, 0x00D7,
+    0x00D8, 0x00D9, 0x00DA, 0x00DB, 0x00DC, 0x00DD, 0x00DE, 0x00DF,
+    0x00E0, 0x00E1, 0x00E2, 0x00E3, 0x00E4, 0x00E5, 0x00E6, 0x00E7,
+    0x00E8, 0x00E9, 0x00EA, 0x00EB, 0x00EC, 0x00ED, 0x00EE, 0x00EF,
+    0x00F0, 0x00F1, 0x00F2, 0x00F3, 0x00F4, 0x00F5, 0x00F6, 0x00F7,
+    0x00F8, 0x00F9, 0x00FA, 0x00FB, 0x00FC, 0x00FD, 0x00FE, 0x00FF,
+];
+
+static CP437: [u16; 128] = [
+    0x00C7, 0x00FC, 0x00E9, 0x00E2, 0x00E4, 0x00E0, 0x00E5, 0x00E7,
+    0x00EA, 0x00EB, 0x00E8, 0x00EF, 0x00EE, 0x00EC, 0x00C4, 0x00C5,
+    0x00C9, 0x00E6, 0x00C6, 0x00F4, 0x00F6, 0x00F2, 0x00FB, 0x00F9,
+    0x00FF, 0x00D6, 0x00DC, 0x00A2, 0x00A3, 0x00A5, 0x20A7, 0x0192,
+    0x00E1, 0x00ED, 0x00F3, 0x00FA, 0x00F1, 0x00D1, 0x00AA, 0x00BA,
+    0x00BF, 0x2310, 0x00AC, 0x00BD, 0x00BC, 0x00A1, 0x00AB, 0x00BB,
+    0x2591, 0x2592, 0x2593, 0x2502, 0x2524, 0x2561, 0x2562, 0x2556,
+    0x2555, 0x2563, 0x2551, 0x2557, 0x255D, 0x255C, 0x255B, 0x2510,
+    0x2514, 0x2534, 0x252C, 0x251C, 0x2500, 0x253C, 0x255E, 0x255F,
+    0x255A, 0x2554, 0x2569, 0x2566, 0x2560, 0x2550, 0x256C, 0x2567,
+    0x2568, 0x2564, 0x2565, 0x2559, 0x2558, 0x2552, 0x2553, 0x256B,
+    0x256A, 0x2518, 0x250C, 0x2588, 0x2584, 0x258C, 0x2590, 0x2580,
+    0x03B1, 0x00DF, 0x0393, 0x03C0, 0x03A3, 0x03C3, 0x00B5, 0x03C4,
+    0x03A6, 0x0398, 0x03A9, 0x03B4, 0x221E, 0x03C6, 0x03B5, 0x2229,
+    0x2261, 0x00B1, 0x2265, 0x2264, 0x2320, 0x2321, 0x00F7, 0x2248,
+    0x00B0, 0x2219, 0x00B7, 0x221A, 0x207F, 0x00B2, 0x25A0, 0x00A0,
+];
+
+static CP850: [u16; 128] = [
+    0x00C7, 0x00FC, 0x00E9, 0x00E2, 0x00E4, 0x00E0, 0x00E5, 0x00E7,
+    0x00EA, 0x00EB, 0x00E8, 0x00EF, 0x00EE, 0x00EC, 0x00C4, 0x00C5,
+    0x00C9, 0x00E6, 0x00C6, 0x00F4, 0x00F6, 0x00F2, 0x00FB, 0x00F9,
+    0x00FF, 0x00D6, 0x00DC, 0x00F8, 0x00A3, 0x00D8, 0x00D7, 0x0192,
+    0x00E1, 0x00ED, 0x00F3, 0x00FA, 0x00F1, 0x00D1, 0x00AA, 0x00BA,
+    0x00BF, 0x00AE, 0x00AC, 0x00BD, 0x00BC, 0x00A1, 0x00AB, 0x00BB,
+    0x2591, 0x2592, 0x2593, 0x2502, 0x2524, 0x00C1, 0x00C2, 0x00C0,
+    0x00A9, 0x2563, 0x2551, 0x2557, 0x255D, 0x00A2, 0x00A5, 0x2510,
+    0x2514, 0x2534, 0x252C, 0x251C, 0x2500, 0x253C, 0x00E3, 0x00C3,
+    0x255A, 0x2554, 0x2569, 0x2566, 0x2560, 0x2550, 0x256C, 0x00A4,
+    0x00F0, 0x00D0, 0x00CA, 0x00CB, 0x00C8, 0x0131, 0x00CD, 0x00CE,
+    0x00CF, 0x2518, 0x250C, 0x2588, 0x2584, 0x00A6, 0x00CC, 0x2580,
+    0x00D3, 0x00DF, 0x00D4, 0x00D2, 0x00F5, 0x00D5, 0x00B5, 0x00FE,
+    0x00DE, 0x00DA, 0x00DB, 0x00D9, 0x00FD, 0x00DD, 0x00AF, 0x00B4,
+    0x00AD, 0x00B1, 0x2017, 0x00BE, 0x00B6, 0x00A7, 0x00F7, 0x00B8,
+    0x00B0, 0x00A8, 0x00B7, 0x00B9, 0x00B3, 0x00B2, 0x25A0, 0x00A0,
+];
+
+enum CodePage {
+    Table(&'static [u16; 128]),
+    Latin1,
+    Utf8,
+}
+
+fn code_page(code_page: u32) -> io::Result<CodePage> {
+    match code_page {
+        1252 => Ok(CodePage::Table(&CP1252)),
+        437 => Ok(CodePage::Table(&CP437)),
+        850 => Ok(CodePage::Table(&CP850)),
+        28591 => Ok(CodePage::Latin1),
+        CP_UTF8 => Ok(CodePage::Utf8),
+        _ => Err(io::Error::new(io::ErrorKind::InvalidInput, "unsupported code page")),
+    }
+}
+
+fn encode_char(cp: &CodePage, c: char) -> Option<u8> {
+    let c = c as u32;
+    if c < 0x80 {
+        return Some(c as u8);
+    }
+    match *cp {
+        CodePage::Table(table) => table.iter().position(|&w| w as u32 == c).map(|i| (i + 0x80) as u8),
+        CodePage::Latin1 if c < 0x100 => Some(c as u8),
+        _ => None,
+    }
+}
+
+impl WCString {
+    /// Decode a string in a code page without calling Win32, for builds where the ```win32``` feature is not
+    /// available. The supported code pages are ```1252```, ```437```, ```850```, ```28591``` (ISO-8859-1) and
+    /// ```65001``` (UTF-8); others, including ```CP_ACP``` (```0```), fail with
+    /// ```io::ErrorKind::InvalidInput```. The bytes are truncated at the first ```nul```, if any.
+    pub fn from_ansi(code_page: u32, bytes: &[u8]) -> io::Result<WCString> {
+        let cp = self::code_page(code_page)?;
+        let bytes = match bytes.iter().position(|&b| b == 0) {
+            Some(i) => &bytes[..i],
+            None => bytes,
+        };
+        let v: Vec<u16> = match cp {
+            CodePage::Table(table) => bytes.iter()
+                .map(|&b| if b < 0x80 { b as u16 } else { table[b as usize - 0x80] })
+                .collect(),
+            CodePage::Latin1 => bytes.iter().map(|&b| b as u16).collect(),
+            CodePage::Utf8 => String::from_utf8_lossy(bytes).encode_utf16().collect(),
+        };
+        Ok(WCString::from_vec_truncate(v))
+    }
+}
+
+impl WCStr {
+    /// Encode this "wide" string in a code page without calling Win32, see ```from_ansi()``` for the supported
+    /// code pages. There are no best fit tables, so ```AnsiPolicy::BestFit``` replaces characters that don't
+    /// exist in the code page with ```?```.
+    ///
+    /// # ```to_ansi()``` example
+    ///     use wcstr::{AnsiPolicy, WCString};
+    ///     let s = WCString::from_str("Caf\u{E9} \u{20AC}5").unwrap();
+    ///     let bytes = s.to_ansi(1252, AnsiPolicy::Strict).unwrap();
+    ///     assert!(bytes == b"Caf\xE9 \x805");
+    ///     assert!(WCString::from_ansi(1252, &bytes).unwrap() == s);
+    ///     let s = WCString::from_str("\u{2554}\u{2550}\u{2557} \u{E9}").unwrap();
+    ///     assert!(s.to_ansi(437, AnsiPolicy::Strict).unwrap() == b"\xC9\xCD\xBB \x82");
+    ///     assert!(s.to_ansi(1252, AnsiPolicy::Replace(b'_')).unwrap() == b"___ \xE9");
+    ///     assert!(s.to_ansi(1252, AnsiPolicy::Strict).is_err());
+    ///     assert!(s.to_ansi(932, AnsiPolicy::BestFit).is_err());
+    pub fn to_ansi(&self, code_page: u32, policy: AnsiPolicy) -> io::Result<Vec<u8>> {
+        let cp = self::code_page(code_page)?;
+        if let CodePage::Utf8 = cp {
+            return match policy {
+                AnsiPolicy::Strict => self.to_string()
+                    .map(String::into_bytes)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+                _ => Ok(self.to_string_lossy().into_bytes()),
+            };
+        }
+        let mut v = Vec::with_capacity(self.len());
+        for c in self.chars() {
+            match (c.ok().and_then(|c| encode_char(&cp, c)), policy) {
+                (Some(b), _) => v.push(b),
+                (None, AnsiPolicy::BestFit) => v.push(b'?'),
+                (None, AnsiPolicy::Replace(b)) => v.push(b),
+                (None, AnsiPolicy::Strict) => {
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, "character not in code page"));
+                },
+            }
+        }
+        Ok(v)
+    }
+}
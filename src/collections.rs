@@ -0,0 +1,240 @@
+
+use ::std;
+use ::std::marker::PhantomData;
+
+use ::{WCStr, WCString};
+use ::cmp::{WCStrOrdering, Ordinal};
+
+/// A map from ```WCString``` keys to values, kept sorted in a flat ```Vec``` according to ```O```.
+///
+/// Lookups are a binary search and accept any ```&WCStr```, so checking a path against an allow/deny
+/// list doesn't allocate. Keys that compare equal under ```O``` (e.g. ```Foo``` and ```FOO``` under
+/// ```OrdinalIgnoreCase```) are the same key. Inserting is ```O(n)```, so this is meant for maps that
+/// are read far more often than they are modified.
+#[derive(Clone, Debug)]
+pub struct WCStrMap<V, O = Ordinal> {
+    entries: Vec<(WCString, V)>,
+    ordering: PhantomData<O>,
+}
+
+/// A set of ```WCString```s, kept sorted in a flat ```Vec``` according to ```O```.
+///
+/// See ```WCStrMap``` for details.
+#[derive(Clone, Debug)]
+pub struct WCStrSet<O = Ordinal> {
+    map: WCStrMap<(), O>,
+}
+
+/// Created with method ```.iter()``` on ```WCStrMap```
+#[derive(Clone, Debug)]
+pub struct WCStrMapIter<'a, V: 'a> {
+    inner: std::slice::Iter<'a, (WCString, V)>,
+}
+
+/// Created with method ```.iter()``` on ```WCStrSet```
+#[derive(Clone, Debug)]
+pub struct WCStrSetIter<'a> {
+    inner: WCStrMapIter<'a, ()>,
+}
+
+impl<V> WCStrMap<V, Ordinal> {
+    /// Create an empty map ordered with ```Ordinal```.
+    pub fn new() -> WCStrMap<V, Ordinal> {
+        WCStrMap::with_ordering(Ordinal)
+    }
+}
+
+impl<V, O: WCStrOrdering> WCStrMap<V, O> {
+    /// Create an empty map ordered with ```O```.
+    ///
+    /// # ```with_ordering()``` example
+    ///     use wcstr::{WCStrMap, WCString, OrdinalIgnoreCase};
+    ///     let mut m = WCStrMap::with_ordering(OrdinalIgnoreCase);
+    ///     m.insert(WCString::from_str("C:\\Windows").unwrap(), "deny");
+    ///     assert!(m.get(WCString::from_str("c:\\WINDOWS").unwrap()) == Some(&"deny"));
+    pub fn with_ordering(_ordering: O) -> WCStrMap<V, O> {
+        WCStrMap {
+            entries: Vec::new(),
+            ordering: PhantomData,
+        }
+    }
+
+    fn search(&self, key: &WCStr) -> Result<usize, usize> {
+        self.entries.binary_search_by(|e| O::cmp(&e.0, key))
+    }
+
+    /// Insert a value, returning the previous value of an equal key.
+    /// If an equal key was already present, that key is kept.
+    pub fn insert(&mut self, key: WCString, value: V) -> Option<V> {
+        match self.search(&key) {
+            Ok(i) => Some(std::mem::replace(&mut self.entries[i].1, value)),
+            Err(i) => {
+                self.entries.insert(i, (key, value));
+                None
+            },
+        }
+    }
+
+    /// Return the value of a key, if present.
+    pub fn get<T>(&self, key: T) -> Option<&V>
+        where T: AsRef<WCStr> {
+        match self.search(key.as_ref()) {
+            Ok(i) => Some(&self.entries[i].1),
+            Err(_) => None,
+        }
+    }
+
+    /// Return the value of a key mutably, if present.
+    pub fn get_mut<T>(&mut self, key: T) -> Option<&mut V>
+        where T: AsRef<WCStr> {
+        match self.search(key.as_ref()) {
+            Ok(i) => Some(&mut self.entries[i].1),
+            Err(_) => None,
+        }
+    }
+
+    /// Check whether a key is present.
+    pub fn contains_key<T>(&self, key: T) -> bool
+        where T: AsRef<WCStr> {
+        self.search(key.as_ref()).is_ok()
+    }
+
+    /// Remove a key, returning its value if it was present.
+    pub fn remove<T>(&mut self, key: T) -> Option<V>
+        where T: AsRef<WCStr> {
+        match self.search(key.as_ref()) {
+            Ok(i) => Some(self.entries.remove(i).1),
+            Err(_) => None,
+        }
+    }
+
+    /// Return the number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterate over the entries in order.
+    pub fn iter<'a>(&'a self) -> WCStrMapIter<'a, V> {
+        WCStrMapIter {
+            inner: self.entries.iter(),
+        }
+    }
+}
+
+impl<V, O: WCStrOrdering + Default> Default for WCStrMap<V, O> {
+    fn default() -> WCStrMap<V, O> {
+        WCStrMap::with_ordering(O::default())
+    }
+}
+
+impl<V, O: WCStrOrdering + Default> std::iter::FromIterator<(WCString, V)> for WCStrMap<V, O> {
+    fn from_iter<I: IntoIterator<Item = (WCString, V)>>(iter: I) -> WCStrMap<V, O> {
+        let mut m = WCStrMap::default();
+        for (k, v) in iter {
+            m.insert(k, v);
+        }
+        m
+    }
+}
+
+impl<'a, V> Iterator for WCStrMapIter<'a, V> {
+    type Item = (&'a WCStr, &'a V);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|e| (e.0.as_wcstr(), &e.1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl WCStrSet<Ordinal> {
+    /// Create an empty set ordered with ```Ordinal```.
+    pub fn new() -> WCStrSet<Ordinal> {
+        WCStrSet::with_ordering(Ordinal)
+    }
+}
+
+impl<O: WCStrOrdering> WCStrSet<O> {
+    /// Create an empty set ordered with ```O```.
+    ///
+    /// # ```with_ordering()``` example
+    ///     use wcstr::{WCStrSet, WCString, Natural};
+    ///     let mut s = WCStrSet::with_ordering(Natural);
+    ///     for name in &["file10.txt", "file2.txt", "File1.txt"] {
+    ///         s.insert(WCString::from_str(name).unwrap());
+    ///     }
+    ///     let sorted: Vec<String> = s.iter().map(|w| w.to_string_lossy()).collect();
+    ///     assert!(sorted == ["File1.txt", "file2.txt", "file10.txt"]);
+    pub fn with_ordering(ordering: O) -> WCStrSet<O> {
+        WCStrSet {
+            map: WCStrMap::with_ordering(ordering),
+        }
+    }
+
+    /// Insert a string, returning ```false``` if an equal string was already present.
+    pub fn insert(&mut self, s: WCString) -> bool {
+        self.map.insert(s, ()).is_none()
+    }
+
+    /// Check whether a string is present.
+    pub fn contains<T>(&self, s: T) -> bool
+        where T: AsRef<WCStr> {
+        self.map.contains_key(s)
+    }
+
+    /// Remove a string, returning ```true``` if it was present.
+    pub fn remove<T>(&mut self, s: T) -> bool
+        where T: AsRef<WCStr> {
+        self.map.remove(s).is_some()
+    }
+
+    /// Return the number of strings.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// is empty
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Iterate over the strings in order.
+    pub fn iter<'a>(&'a self) -> WCStrSetIter<'a> {
+        WCStrSetIter {
+            inner: self.map.iter(),
+        }
+    }
+}
+
+impl<O: WCStrOrdering + Default> Default for WCStrSet<O> {
+    fn default() -> WCStrSet<O> {
+        WCStrSet::with_ordering(O::default())
+    }
+}
+
+impl<O: WCStrOrdering + Default> std::iter::FromIterator<WCString> for WCStrSet<O> {
+    fn from_iter<I: IntoIterator<Item = WCString>>(iter: I) -> WCStrSet<O> {
+        let mut s = WCStrSet::default();
+        for w in iter {
+            s.insert(w);
+        }
+        s
+    }
+}
+
+impl<'a> Iterator for WCStrSetIter<'a> {
+    type Item = &'a WCStr;
+    fn next(&mut self) -> Option<&'a WCStr> {
+        self.inner.next().map(|e| e.0)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
@@ -0,0 +1,157 @@
+
+use ::WCStr;
+use ::CursorError;
+use ::error;
+use ::wcstr;
+
+/// A cursor over a ```&WCStr``` for writing parsers, tracking the offset in ```u16``` units along with
+/// the line and column.
+///
+/// Lines are separated by ```\n```, and both lines and columns start at 1.
+///
+/// ```command_line_args()``` and the drive and UNC prefix parsing of the path methods are built on it.
+///
+/// # ```WCStrCursor``` example
+///     use wcstr::{WCString, WCStrCursor};
+///     let s = WCString::from_str("key = value").unwrap();
+///     let mut cursor = WCStrCursor::new(&s);
+///     let key = cursor.take_while(|w| w != b' ' as u16 && w != b'=' as u16);
+///     cursor.skip_while(|w| w == b' ' as u16);
+///     cursor.expect(b'=' as u16).unwrap();
+///     cursor.skip_while(|w| w == b' ' as u16);
+///     assert!(key == &s.to_slice()[..3]);
+///     assert!(cursor.rest() == "value");
+///     let err = cursor.expect(b';' as u16).unwrap_err();
+///     assert!(err.offset() == 6 && err.column() == 7);
+///     assert!(err.to_string() == "expected ';', found 'v' at line 1, column 7");
+#[derive(Clone, Copy, Debug)]
+pub struct WCStrCursor<'a> {
+    s: &'a WCStr,
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> WCStrCursor<'a> {
+    /// Create a cursor at the start of ```s```.
+    pub fn new(s: &'a WCStr) -> WCStrCursor<'a> {
+        WCStrCursor {
+            s: s,
+            offset: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    /// Return the current position in u16 units.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Return the current line, starting at 1.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Return the current column in u16 units, starting at 1.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Check whether the cursor is at the end of the string.
+    pub fn is_eof(&self) -> bool {
+        self.offset == self.s.len()
+    }
+
+    /// Return the rest of the string, from the current position.
+    pub fn rest(&self) -> &'a WCStr {
+        let s: &'a WCStr = self.s;
+        unsafe { wcstr::from_units_with_nul(&s.to_slice_with_nul()[self.offset..]) }
+    }
+
+    /// Return the unit at the current position without advancing.
+    pub fn peek(&self) -> Option<u16> {
+        self.peek_at(0)
+    }
+
+    /// Return the unit ```n``` units after the current position without advancing.
+    pub fn peek_at(&self, n: usize) -> Option<u16> {
+        self.s.to_slice().get(self.offset + n).cloned()
+    }
+
+    /// Return the unit at the current position and advance past it.
+    pub fn advance(&mut self) -> Option<u16> {
+        let w = self.peek();
+        if let Some(w) = w {
+            self.offset += 1;
+            if w == b'\n' as u16 {
+                self.line += 1;
+                self.column = 1;
+            }
+            else {
+                self.column += 1;
+            }
+        }
+        w
+    }
+
+    /// Advance past the unit at the current position if it is ```w```, returning whether it was.
+    pub fn eat(&mut self, w: u16) -> bool {
+        if self.peek() == Some(w) {
+            self.advance();
+            true
+        }
+        else {
+            false
+        }
+    }
+
+    /// Advance past the unit at the current position if it is ```w```, or return an error at the current
+    /// position.
+    pub fn expect(&mut self, w: u16) -> Result<(), CursorError> {
+        if self.eat(w) {
+            Ok(())
+        }
+        else {
+            Err(self.error(Some(w)))
+        }
+    }
+
+    /// Advance past ```s``` if the string continues with it, or return an error at the first unit that
+    /// does not match. The cursor is not moved on error.
+    pub fn expect_slice<T>(&mut self, s: T) -> Result<(), CursorError>
+        where T: AsRef<[u16]> {
+        let s = s.as_ref();
+        let mut ahead = *self;
+        for &w in s {
+            ahead.expect(w)?;
+        }
+        *self = ahead;
+        Ok(())
+    }
+
+    /// Advance while ```pred``` holds, returning the units advanced past.
+    pub fn take_while<F>(&mut self, mut pred: F) -> &'a [u16]
+        where F: FnMut(u16) -> bool {
+        let start = self.offset;
+        while let Some(w) = self.peek() {
+            if !pred(w) {
+                break;
+            }
+            self.advance();
+        }
+        let s: &'a WCStr = self.s;
+        &s.to_slice()[start..self.offset]
+    }
+
+    /// Advance while ```pred``` holds, returning the number of units advanced past.
+    pub fn skip_while<F>(&mut self, pred: F) -> usize
+        where F: FnMut(u16) -> bool {
+        self.take_while(pred).len()
+    }
+
+    /// Create an error at the current position, for errors detected by the caller.
+    pub fn error(&self, expected: Option<u16>) -> CursorError {
+        error::cursor(self.offset, self.line, self.column, expected, self.peek())
+    }
+}
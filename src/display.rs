@@ -0,0 +1,120 @@
+
+use ::std;
+use ::std::fmt::Write;
+
+use ::path::is_separator;
+use ::search;
+
+/// Which parts of a "wide" string ```display_redacted()``` should mask.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RedactPolicy<'a> {
+    /// Mask the whole string.
+    All,
+    /// Mask every path component except the last one, e.g. ```***\secret.txt```.
+    /// Both ```\``` and ```/``` are treated as separators.
+    AllButLastComponent,
+    /// Mask everything after the first occurrence of a marker, e.g. ```password=***```.
+    /// Nothing is masked if the marker is not found.
+    After(&'a str),
+}
+
+/// Created with method ```.display_redacted(policy)```
+#[derive(Clone, Copy, Debug)]
+pub struct RedactedDisplay<'a> {
+    s: &'a [u16],
+    policy: RedactPolicy<'a>,
+}
+
+/// Created with method ```.as_display()```
+///
+/// Formats a "wide" string lossily, writing unpaired surrogates as ```U+FFFD REPLACEMENT CHARACTER```.
+#[derive(Debug)]
+pub struct WCDisplay {
+    inner: [u16],
+}
+
+const MASK: &str = "***";
+
+pub fn redacted<'a>(s: &'a [u16], policy: RedactPolicy<'a>) -> RedactedDisplay<'a> {
+    RedactedDisplay {
+        s: s,
+        policy: policy,
+    }
+}
+
+pub fn display<'a>(s: &'a [u16]) -> &'a WCDisplay {
+    unsafe { std::mem::transmute(s) }
+}
+
+/// Write ```s``` into ```f```, replacing unpaired surrogates with ```U+FFFD REPLACEMENT CHARACTER```.
+/// Honors the width, fill, alignment and precision of ```f``` like ```str``` does, without allocating.
+pub fn write_lossy(f: &mut std::fmt::Formatter, s: &[u16]) -> std::fmt::Result {
+    if f.width().is_none() && f.precision().is_none() {
+        return write_chars(f, s, usize::MAX);
+    }
+    let max = f.precision().unwrap_or(usize::MAX);
+    let count = std::cmp::min(std::char::decode_utf16(s.iter().cloned()).count(), max);
+    let pad = f.width().map_or(0, |width| width.saturating_sub(count));
+    let (before, after) = match f.align() {
+        Some(std::fmt::Alignment::Right) => (pad, 0),
+        Some(std::fmt::Alignment::Center) => (pad / 2, pad - pad / 2),
+        _ => (0, pad),
+    };
+    let fill = f.fill();
+    for _ in 0..before {
+        f.write_char(fill)?;
+    }
+    write_chars(f, s, count)?;
+    for _ in 0..after {
+        f.write_char(fill)?;
+    }
+    Ok(())
+}
+
+/// Write at most ```max``` chars of ```s``` into ```f```, ignoring its formatting options.
+fn write_chars(f: &mut std::fmt::Formatter, s: &[u16], max: usize) -> std::fmt::Result {
+    for c in std::char::decode_utf16(s.iter().cloned()).take(max) {
+        f.write_char(c.unwrap_or(std::char::REPLACEMENT_CHARACTER))?;
+    }
+    Ok(())
+}
+
+impl<'a> std::fmt::Display for RedactedDisplay<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.policy {
+            RedactPolicy::All => f.write_str(MASK),
+            RedactPolicy::AllButLastComponent => {
+                match self.s.iter().rposition(|&w| is_separator(w)) {
+                    Some(i) => {
+                        f.write_str(MASK)?;
+                        write_chars(f, &self.s[i..], usize::MAX)
+                    },
+                    None => write_chars(f, self.s, usize::MAX),
+                }
+            },
+            RedactPolicy::After(marker) => {
+                let marker: Vec<u16> = marker.encode_utf16().collect();
+                match search::find(self.s, &marker) {
+                    Some(i) => {
+                        write_chars(f, &self.s[..i + marker.len()], usize::MAX)?;
+                        f.write_str(MASK)
+                    },
+                    None => write_chars(f, self.s, usize::MAX),
+                }
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for WCDisplay {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write_lossy(f, &self.inner)
+    }
+}
+
+#[cfg(feature = "log")]
+impl<'a> ::log::kv::ToValue for &'a WCDisplay {
+    fn to_value<'b>(&'b self) -> ::log::kv::Value<'b> {
+        ::log::kv::Value::from_display(self)
+    }
+}
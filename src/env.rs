@@ -0,0 +1,218 @@
+
+use ::std;
+
+use ::std::ffi::OsStr;
+
+use ::{WCStr, WCString, WCStrMap, WCStrMapIter, OrdinalIgnoreCase, EnvVarError, EnvVarErrorKind};
+use ::error;
+use ::os;
+
+/// An environment variable map with Windows semantics.
+///
+/// * Names are compared case-insensitively, like ```OrdinalIgnoreCase```, and the first spelling of a
+///   name is kept.
+/// * Variables are always kept sorted, so ```to_block()``` produces the canonical block ```CreateProcessW```
+///   expects regardless of the order they were inserted in.
+#[derive(Clone, Debug, Default)]
+pub struct EnvMap {
+    vars: WCStrMap<WCString, OrdinalIgnoreCase>,
+}
+
+/// A finished environment block for the ```lpEnvironment``` parameter of ```CreateProcessW```.
+///
+/// The block holds ```NAME=VALUE``` entries sorted case-insensitively, each ```nul``` terminated, followed by
+/// one more ```nul```. It must be passed with ```CREATE_UNICODE_ENVIRONMENT```.
+///
+/// # ```EnvironmentBlock``` example
+///     use wcstr::{EnvironmentBlock, EnvVarErrorKind};
+///     let block = EnvironmentBlock::new(vec![("TEMP", "C:\\Temp"), ("Path", "C:\\bin")]).unwrap();
+///     let expected: Vec<u16> = "Path=C:\\bin\0TEMP=C:\\Temp\0\0".encode_utf16().collect();
+///     assert!(block.as_slice_with_nul() == &expected[..]);
+///     let err = EnvironmentBlock::new(vec![("A=B", "1")]).unwrap_err();
+///     assert!(err.kind() == EnvVarErrorKind::EqualsInName);
+///     let err = EnvironmentBlock::new(vec![("A", "1"), ("B", "\0")]).unwrap_err();
+///     assert!(err.index() == 1 && err.kind() == EnvVarErrorKind::Nul);
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct EnvironmentBlock {
+    inner: Vec<u16>,
+}
+
+fn is_equals(w: u16) -> bool {
+    w == b'=' as u16
+}
+
+impl EnvMap {
+    /// Create an empty environment.
+    pub fn new() -> EnvMap {
+        EnvMap {
+            vars: WCStrMap::with_ordering(OrdinalIgnoreCase),
+        }
+    }
+
+    /// Create an environment from the variables of the current process.
+    /// Variables containing ```nul``` are skipped.
+    pub fn from_current() -> EnvMap {
+        let mut env = EnvMap::new();
+        for (name, value) in std::env::vars_os() {
+            if let (Ok(name), Ok(value)) = (WCString::from_str(name), WCString::from_str(value)) {
+                env.insert(name, value);
+            }
+        }
+        env
+    }
+
+    /// Parse a double-nul terminated environment block of ```NAME=VALUE``` entries, as returned by
+    /// ```GetEnvironmentStringsW```. Parsing stops at the first empty entry or at the end of ```block```.
+    ///
+    /// * The name ends at the first ```=``` after its first unit, so the hidden per-drive variables such as
+    ///   ```=C:=C:\Windows``` are kept.
+    /// * Entries without a ```=``` are skipped.
+    ///
+    /// # ```from_block()``` example
+    ///     use wcstr::{EnvMap, WCString};
+    ///     let block: Vec<u16> = "=C:=C:\\\0Path=C:\\bin\0\0".encode_utf16().collect();
+    ///     let env = EnvMap::from_block(&block);
+    ///     assert!(env.len() == 2);
+    ///     assert!(env.get(WCString::from_str("PATH").unwrap()).unwrap() == "C:\\bin");
+    ///     assert!(env.get(WCString::from_str("=c:").unwrap()).unwrap() == "C:\\");
+    pub fn from_block<T>(block: T) -> EnvMap
+        where T: AsRef<[u16]> {
+        let mut env = EnvMap::new();
+        for entry in block.as_ref().split(|&w| w == 0) {
+            if entry.is_empty() {
+                break;
+            }
+            let eq = match entry.iter().skip(1).position(|&w| is_equals(w)) {
+                Some(eq) => eq + 1,
+                None => continue,
+            };
+            let name = unsafe { WCString::from_vec_unchecked(entry[..eq].to_vec()) };
+            let value = unsafe { WCString::from_vec_unchecked(entry[eq + 1..].to_vec()) };
+            env.insert(name, value);
+        }
+        env
+    }
+
+    /// Build the double-nul terminated environment block for ```CreateProcessW``` with
+    /// ```CREATE_UNICODE_ENVIRONMENT```, sorted case-insensitively as Windows requires.
+    ///
+    /// # ```to_block()``` example
+    ///     use wcstr::{EnvMap, WCString};
+    ///     let mut env = EnvMap::new();
+    ///     env.insert(WCString::from_str("b").unwrap(), WCString::from_str("2").unwrap());
+    ///     env.insert(WCString::from_str("A").unwrap(), WCString::from_str("1").unwrap());
+    ///     let block: Vec<u16> = "A=1\0b=2\0\0".encode_utf16().collect();
+    ///     assert!(env.to_block() == block);
+    ///     assert!(EnvMap::new().to_block() == [0, 0]);
+    pub fn to_block(&self) -> Vec<u16> {
+        let mut block = Vec::new();
+        for (name, value) in self.vars.iter() {
+            block.extend_from_slice(name.to_slice());
+            block.push(b'=' as u16);
+            block.extend_from_slice(value.to_slice_with_nul());
+        }
+        if block.is_empty() {
+            block.push(0);
+        }
+        block.push(0);
+        block
+    }
+
+    /// Set a variable, returning its previous value.
+    ///
+    /// * This will assert if ```name``` is empty or contains ```=``` after its first unit.
+    pub fn insert(&mut self, name: WCString, value: WCString) -> Option<WCString> {
+        assert!(!name.is_empty() && !name.to_slice()[1..].iter().any(|&w| is_equals(w)));
+        self.vars.insert(name, value)
+    }
+
+    /// Return the value of a variable, if set.
+    pub fn get<T>(&self, name: T) -> Option<&WCStr>
+        where T: AsRef<WCStr> {
+        self.vars.get(name).map(|v| v.as_wcstr())
+    }
+
+    /// Check whether a variable is set.
+    pub fn contains_key<T>(&self, name: T) -> bool
+        where T: AsRef<WCStr> {
+        self.vars.contains_key(name)
+    }
+
+    /// Remove a variable, returning its value if it was set.
+    pub fn remove<T>(&mut self, name: T) -> Option<WCString>
+        where T: AsRef<WCStr> {
+        self.vars.remove(name)
+    }
+
+    /// Return the number of variables.
+    pub fn len(&self) -> usize {
+        self.vars.len()
+    }
+
+    /// is empty
+    pub fn is_empty(&self) -> bool {
+        self.vars.is_empty()
+    }
+
+    /// Iterate over the variables in canonical order.
+    pub fn iter<'a>(&'a self) -> WCStrMapIter<'a, WCString> {
+        self.vars.iter()
+    }
+
+    /// Build the environment block for these variables.
+    pub fn to_environment_block(&self) -> EnvironmentBlock {
+        EnvironmentBlock {
+            inner: self.to_block(),
+        }
+    }
+}
+
+impl EnvironmentBlock {
+    /// Build an environment block from ```(name, value)``` pairs. If a name appears more than once,
+    /// the last value is kept.
+    ///
+    /// * Names can't be empty or contain ```=``` after their first unit, and neither names nor values can
+    ///   contain ```nul```; the first offending pair is reported in the ```EnvVarError```.
+    pub fn new<I, K, V>(vars: I) -> Result<EnvironmentBlock, EnvVarError>
+        where I: IntoIterator<Item = (K, V)>, K: AsRef<OsStr>, V: AsRef<OsStr> {
+        let mut env = EnvMap::new();
+        for (index, (name, value)) in vars.into_iter().enumerate() {
+            let name: Vec<u16> = os::encode_wide(name.as_ref()).collect();
+            let value: Vec<u16> = os::encode_wide(value.as_ref()).collect();
+            if name.is_empty() {
+                return Err(error::env_var(index, EnvVarErrorKind::EmptyName));
+            }
+            if name[1..].iter().any(|&w| is_equals(w)) {
+                return Err(error::env_var(index, EnvVarErrorKind::EqualsInName));
+            }
+            match (WCString::from_vec(name), WCString::from_vec(value)) {
+                (Ok(name), Ok(value)) => {
+                    env.insert(name, value);
+                },
+                _ => return Err(error::env_var(index, EnvVarErrorKind::Nul)),
+            }
+        }
+        Ok(env.to_environment_block())
+    }
+
+    /// Return a raw pointer to the block, for ```lpEnvironment```.
+    pub fn as_ptr(&self) -> *const u16 {
+        self.inner.as_ptr()
+    }
+
+    /// Return the block as a slice of ```u16```s, including the final ```nul```s.
+    pub fn as_slice_with_nul(&self) -> &[u16] {
+        &self.inner
+    }
+
+    /// Consume the block, returning the underlying buffer.
+    pub fn into_vec(self) -> Vec<u16> {
+        self.inner
+    }
+}
+
+impl<'a> From<&'a EnvMap> for EnvironmentBlock {
+    fn from(env: &'a EnvMap) -> EnvironmentBlock {
+        env.to_environment_block()
+    }
+}
@@ -9,6 +9,80 @@ pub struct NulError(usize, Option<Vec<u16>>);
 #[derive(Clone, PartialEq, Debug)]
 pub struct NoNulError(Option<Vec<u16>>);
 
+/// An error returned when a UTF-32 code unit is not a Unicode scalar value, or is nul.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Utf32Error(usize, u32);
+
+/// An error returned when a "wide" string is not well-formed UTF-16.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Utf16Error {
+    offset: usize,
+    kind: Utf16ErrorKind,
+}
+
+/// The kind of ill-formed UTF-16 found, see ```Utf16Error```.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Utf16ErrorKind {
+    /// A high surrogate that is not followed by a low surrogate.
+    LoneHighSurrogate,
+    /// A low surrogate that is not preceded by a high surrogate.
+    LoneLowSurrogate,
+}
+
+/// An error returned by ```to_string_checked()```, carrying the position of the first unpaired surrogate
+/// and the part of the string before it.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ToStringError {
+    error: Utf16Error,
+    prefix: String,
+}
+
+/// An error returned when a "wide" string does not fit in a buffer.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BufferTooSmallError(usize);
+
+/// An error returned by ```WCStrCursor``` when the input does not match what was expected.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CursorError {
+    offset: usize,
+    line: usize,
+    column: usize,
+    expected: Option<u16>,
+    found: Option<u16>,
+}
+
+/// An error returned by ```EnvironmentBlock::new()``` when a variable can't be stored in an environment block.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct EnvVarError {
+    index: usize,
+    kind: EnvVarErrorKind,
+}
+
+/// The reason a variable was rejected, see ```EnvVarError```.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum EnvVarErrorKind {
+    /// The name is empty.
+    EmptyName,
+    /// The name contains ```=``` after its first unit.
+    EqualsInName,
+    /// The name or the value contains ```nul```.
+    Nul,
+}
+
+/// An error returned when a byte slice has an odd length or contains an unexpected ```nul```.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FromBytesError {
+    len: usize,
+    nul_position: Option<usize>,
+}
+
+/// An error returned when a "wide" string is too long for a counted string such as ```UNICODE_STRING```.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TooLongError {
+    len: usize,
+    max: usize,
+}
+
 pub fn nul(p: usize, s: Option<Vec<u16>>) -> NulError {
     NulError(p, s)
 }
@@ -17,6 +91,79 @@ pub fn no_nul(s: Option<Vec<u16>>) -> NoNulError {
     NoNulError(s)
 }
 
+pub fn utf32(p: usize, v: u32) -> Utf32Error {
+    Utf32Error(p, v)
+}
+
+pub fn utf16(offset: usize, kind: Utf16ErrorKind) -> Utf16Error {
+    Utf16Error {
+        offset: offset,
+        kind: kind,
+    }
+}
+
+pub fn to_string(error: Utf16Error, prefix: String) -> ToStringError {
+    ToStringError {
+        error: error,
+        prefix: prefix,
+    }
+}
+
+pub fn buffer_too_small(required: usize) -> BufferTooSmallError {
+    BufferTooSmallError(required)
+}
+
+pub fn cursor(offset: usize, line: usize, column: usize, expected: Option<u16>, found: Option<u16>) -> CursorError {
+    CursorError {
+        offset: offset,
+        line: line,
+        column: column,
+        expected: expected,
+        found: found,
+    }
+}
+
+pub fn env_var(index: usize, kind: EnvVarErrorKind) -> EnvVarError {
+    EnvVarError {
+        index: index,
+        kind: kind,
+    }
+}
+
+pub fn from_bytes(len: usize, nul_position: Option<usize>) -> FromBytesError {
+    FromBytesError {
+        len: len,
+        nul_position: nul_position,
+    }
+}
+
+pub fn too_long(len: usize, max: usize) -> TooLongError {
+    TooLongError {
+        len: len,
+        max: max,
+    }
+}
+
+/// Panic if ```v``` contains a ```nul```, when the ```extra-checks``` feature is enabled in a debug build.
+pub fn check_no_nul(func: &str, v: &[u16]) {
+    if cfg!(all(feature = "extra-checks", debug_assertions)) {
+        if let Some(i) = v.iter().position(|&w| w == 0) {
+            panic!("{}: unexpected nul at position {}", func, i);
+        }
+    }
+}
+
+/// Panic if ```v``` is not terminated with its only ```nul```, when the ```extra-checks``` feature is
+/// enabled in a debug build.
+pub fn check_nul_terminated(func: &str, v: &[u16]) {
+    if cfg!(all(feature = "extra-checks", debug_assertions)) {
+        match v.split_last() {
+            Some((&0, rest)) => check_no_nul(func, rest),
+            _ => panic!("{}: missing nul terminator", func),
+        }
+    }
+}
+
 impl NulError {
     /// Return the position of the nul in u16 units.
     pub fn nul_position(&self) -> usize {
@@ -64,3 +211,246 @@ impl std::error::Error for NoNulError {
     }
 }
 
+impl Utf32Error {
+    /// Return the position of the invalid code unit.
+    pub fn position(&self) -> usize {
+        self.0
+    }
+
+    /// Return the invalid code unit.
+    pub fn value(&self) -> u32 {
+        self.1
+    }
+}
+
+impl std::fmt::Display for Utf32Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.1 == 0 {
+            write!(f, "nul found at position: {}", self.0)
+        }
+        else {
+            write!(f, "invalid UTF-32 code unit {:#X} at position: {}", self.1, self.0)
+        }
+    }
+}
+
+impl std::error::Error for Utf32Error {
+    fn description(&self) -> &str {
+        "invalid UTF-32"
+    }
+}
+
+impl Utf16Error {
+    /// Return the position of the unpaired surrogate in u16 units.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Return the kind of ill-formed UTF-16 found.
+    pub fn kind(&self) -> Utf16ErrorKind {
+        self.kind
+    }
+}
+
+impl std::fmt::Display for Utf16Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.kind {
+            Utf16ErrorKind::LoneHighSurrogate => write!(f, "unpaired high surrogate at position: {}", self.offset),
+            Utf16ErrorKind::LoneLowSurrogate => write!(f, "unpaired low surrogate at position: {}", self.offset),
+        }
+    }
+}
+
+impl std::error::Error for Utf16Error {
+    fn description(&self) -> &str {
+        "invalid UTF-16"
+    }
+}
+
+impl ToStringError {
+    /// Return the position of the unpaired surrogate in u16 units.
+    pub fn offset(&self) -> usize {
+        self.error.offset
+    }
+
+    /// Return the underlying ```Utf16Error```.
+    pub fn utf16_error(&self) -> Utf16Error {
+        self.error
+    }
+
+    /// Return the part of the string before the unpaired surrogate.
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Consume this error, returning the part of the string before the unpaired surrogate.
+    pub fn into_prefix(self) -> String {
+        self.prefix
+    }
+}
+
+impl std::fmt::Display for ToStringError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} (after {:?})", self.error, self.prefix)
+    }
+}
+
+impl std::error::Error for ToStringError {
+    fn description(&self) -> &str {
+        "invalid UTF-16"
+    }
+
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl BufferTooSmallError {
+    /// Return the buffer size needed in u16 units, including the nul terminator.
+    pub fn required(&self) -> usize {
+        self.0
+    }
+}
+
+impl std::fmt::Display for BufferTooSmallError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "buffer too small, {} u16 units required", self.0)
+    }
+}
+
+impl std::error::Error for BufferTooSmallError {
+    fn description(&self) -> &str {
+        "buffer too small"
+    }
+}
+
+impl CursorError {
+    /// Return the position of the error in u16 units.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Return the line of the error, starting at 1.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Return the column of the error in u16 units, starting at 1.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Return the unit that was expected, if a specific one was.
+    pub fn expected(&self) -> Option<u16> {
+        self.expected
+    }
+
+    /// Return the unit that was found, or ```None``` at the end of the string.
+    pub fn found(&self) -> Option<u16> {
+        self.found
+    }
+}
+
+fn write_unit(f: &mut std::fmt::Formatter, w: Option<u16>) -> std::fmt::Result {
+    match w.and_then(|w| std::char::from_u32(w as u32)) {
+        Some(c) if !c.is_control() => write!(f, "{:?}", c),
+        Some(c) => write!(f, "{:#06X}", c as u32),
+        None => match w {
+            Some(w) => write!(f, "{:#06X}", w),
+            None => write!(f, "end of string"),
+        },
+    }
+}
+
+impl std::fmt::Display for CursorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.expected.is_some() {
+            write!(f, "expected ")?;
+            write_unit(f, self.expected)?;
+            write!(f, ", ")?;
+        }
+        write!(f, "found ")?;
+        write_unit(f, self.found)?;
+        write!(f, " at line {}, column {}", self.line, self.column)
+    }
+}
+
+impl std::error::Error for CursorError {
+    fn description(&self) -> &str {
+        "unexpected input"
+    }
+}
+
+impl EnvVarError {
+    /// Return the position of the rejected variable in the input.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Return the reason the variable was rejected.
+    pub fn kind(&self) -> EnvVarErrorKind {
+        self.kind
+    }
+}
+
+impl std::fmt::Display for EnvVarError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.kind {
+            EnvVarErrorKind::EmptyName => write!(f, "empty variable name at index: {}", self.index),
+            EnvVarErrorKind::EqualsInName => write!(f, "'=' in variable name at index: {}", self.index),
+            EnvVarErrorKind::Nul => write!(f, "nul in variable at index: {}", self.index),
+        }
+    }
+}
+
+impl std::error::Error for EnvVarError {
+    fn description(&self) -> &str {
+        "invalid environment variable"
+    }
+}
+
+impl FromBytesError {
+    /// Return the position of the unexpected nul in u16 units, or ```None``` if the byte length was odd.
+    pub fn nul_position(&self) -> Option<usize> {
+        self.nul_position
+    }
+}
+
+impl std::fmt::Display for FromBytesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.nul_position {
+            Some(p) => write!(f, "nul found at position: {}", p),
+            None => write!(f, "odd number of bytes: {}", self.len),
+        }
+    }
+}
+
+impl std::error::Error for FromBytesError {
+    fn description(&self) -> &str {
+        "invalid UTF-16 bytes"
+    }
+}
+
+impl TooLongError {
+    /// Return the length of the string in u16 units.
+    pub fn length(&self) -> usize {
+        self.len
+    }
+
+    /// Return the maximum length in u16 units.
+    pub fn max_length(&self) -> usize {
+        self.max
+    }
+}
+
+impl std::fmt::Display for TooLongError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "string too long, {} u16 units (maximum: {})", self.len, self.max)
+    }
+}
+
+impl std::error::Error for TooLongError {
+    fn description(&self) -> &str {
+        "string too long"
+    }
+}
@@ -0,0 +1,49 @@
+
+use ::std::fs;
+use ::std::io;
+use ::std::path::Path;
+
+use ::{WCStr, WCString};
+
+const BOM: [u8; 2] = [0xFF, 0xFE];
+
+/// Read a UTF-16 text file into a ```WCString```.
+///
+/// * A byte order mark selects UTF-16LE or UTF-16BE and is dropped; without one the file is read as UTF-16LE.
+/// * A ```nul``` terminator at the end of the file is dropped.
+/// * An odd file length or any other ```nul``` fails with ```io::ErrorKind::InvalidData```.
+///
+/// # ```read_wcstring()``` example
+///     use wcstr::{read_wcstring, WCString};
+///     let path = std::env::temp_dir().join("wcstr-read_wcstring.txt");
+///     let s = WCString::from_str("line 1\r\nline 2").unwrap();
+///     s.write_to(std::fs::File::create(&path).unwrap(), true).unwrap();
+///     assert!(read_wcstring(&path).unwrap() == s);
+///     std::fs::remove_file(&path).unwrap();
+pub fn read_wcstring<P>(path: P) -> io::Result<WCString>
+    where P: AsRef<Path> {
+    let bytes = fs::read(path)?;
+    WCString::from_utf16_bytes(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+impl WCStr {
+    /// Write this "wide" string as UTF-16LE text, without the ```nul``` terminator.
+    /// With ```with_bom```, the ```FF FE``` byte order mark that most Windows tools expect is written first.
+    ///
+    /// # ```write_to()``` example
+    ///     use wcstr::WCString;
+    ///     let mut out = Vec::new();
+    ///     WCString::from_str("Hi").unwrap().write_to(&mut out, true).unwrap();
+    ///     assert!(out == [0xFF, 0xFE, b'H', 0, b'i', 0]);
+    pub fn write_to<W>(&self, mut writer: W, with_bom: bool) -> io::Result<()>
+        where W: io::Write {
+        let mut bytes = Vec::with_capacity(self.len() * 2 + 2);
+        if with_bom {
+            bytes.extend_from_slice(&BOM);
+        }
+        for &w in self.to_slice() {
+            bytes.extend_from_slice(&w.to_le_bytes());
+        }
+        writer.write_all(&bytes)
+    }
+}
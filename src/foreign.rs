@@ -0,0 +1,204 @@
+
+use ::std;
+use ::std::borrow::Borrow;
+use ::std::marker::PhantomData;
+use ::std::ops::Deref;
+use ::std::os::raw::c_void;
+
+use ::WCStr;
+use ::wcstr;
+
+#[allow(non_snake_case)]
+#[link(name = "ole32")]
+extern "system" {
+    fn CoTaskMemFree(pv: *mut c_void);
+}
+
+#[allow(non_snake_case)]
+#[link(name = "kernel32")]
+extern "system" {
+    fn LocalFree(hMem: *mut c_void) -> *mut c_void;
+    fn GlobalFree(hMem: *mut c_void) -> *mut c_void;
+    fn GetProcessHeap() -> *mut c_void;
+    fn HeapFree(hHeap: *mut c_void, dwFlags: u32, lpMem: *mut c_void) -> i32;
+}
+
+/// How a ```ForeignWCString``` frees its string.
+///
+/// # ```Deleter``` example
+///     # extern crate wcstr;
+///     use wcstr::{Deleter, ForeignWCString};
+///     struct Leak;
+///     impl Deleter for Leak {
+///         unsafe fn free(_: *mut u16) {}
+///     }
+///     # fn main() {
+///     static NAME: [u16; 3] = [b'h' as u16, b'i' as u16, 0];
+///     let s = unsafe { ForeignWCString::<Leak>::from_raw(NAME.as_ptr() as *mut u16) }.unwrap();
+///     assert!(s.as_wcstr() == "hi");
+///     # }
+pub trait Deleter {
+    /// Free a non-null string that was allocated by the matching allocator.
+    unsafe fn free(ptr: *mut u16);
+}
+
+/// Frees with ```CoTaskMemFree```.
+#[derive(Clone, Copy, Debug)]
+pub struct CoTaskMem;
+
+/// Frees with ```LocalFree```.
+#[derive(Clone, Copy, Debug)]
+pub struct LocalMem;
+
+/// Frees with ```GlobalFree```.
+#[derive(Clone, Copy, Debug)]
+pub struct GlobalMem;
+
+/// Frees with ```HeapFree``` on ```GetProcessHeap()```.
+#[derive(Clone, Copy, Debug)]
+pub struct ProcessHeap;
+
+impl Deleter for CoTaskMem {
+    unsafe fn free(ptr: *mut u16) {
+        CoTaskMemFree(ptr as *mut c_void);
+    }
+}
+
+impl Deleter for LocalMem {
+    unsafe fn free(ptr: *mut u16) {
+        LocalFree(ptr as *mut c_void);
+    }
+}
+
+impl Deleter for GlobalMem {
+    unsafe fn free(ptr: *mut u16) {
+        GlobalFree(ptr as *mut c_void);
+    }
+}
+
+impl Deleter for ProcessHeap {
+    unsafe fn free(ptr: *mut u16) {
+        HeapFree(GetProcessHeap(), 0, ptr as *mut c_void);
+    }
+}
+
+/// An owned "wide" string allocated by an API that expects the caller to free it, freed on drop with the
+/// ```Deleter``` ```D```.
+pub struct ForeignWCString<D: Deleter> {
+    ptr: *mut u16,
+    len: usize,
+    deleter: PhantomData<D>,
+}
+
+/// An owned "wide" string allocated by COM or the shell with ```CoTaskMemAlloc```, such as the result of
+/// ```SHGetKnownFolderPath``` or ```StringFromCLSID```, freed with ```CoTaskMemFree``` on drop.
+///
+/// # ```CoTaskWCString``` example
+///     # extern crate wcstr;
+///     use wcstr::CoTaskWCString;
+///     #[link(name = "ole32")]
+///     extern "system" {
+///         fn StringFromCLSID(rclsid: *const [u8; 16], lplpsz: *mut *mut u16) -> i32;
+///     }
+///     # fn main() {
+///     let clsid = [0u8; 16];
+///     let mut p = std::ptr::null_mut();
+///     assert!(unsafe { StringFromCLSID(&clsid, &mut p) } == 0);
+///     let s = unsafe { CoTaskWCString::from_raw(p) }.unwrap();
+///     assert!(s.as_wcstr() == "{00000000-0000-0000-0000-000000000000}");
+///     # }
+pub type CoTaskWCString = ForeignWCString<CoTaskMem>;
+
+/// An owned "wide" string allocated with ```LocalAlloc```, such as the message returned by ```FormatMessageW```
+/// with ```FORMAT_MESSAGE_ALLOCATE_BUFFER```, freed with ```LocalFree``` on drop.
+///
+/// # ```LocalWCString``` example
+///     # extern crate wcstr;
+///     use wcstr::LocalWCString;
+///     #[link(name = "kernel32")]
+///     extern "system" {
+///         fn FormatMessageW(dwFlags: u32, lpSource: *const u8, dwMessageId: u32, dwLanguageId: u32,
+///                           lpBuffer: *mut *mut u16, nSize: u32, Arguments: *mut u8) -> u32;
+///     }
+///     # fn main() {
+///     // FORMAT_MESSAGE_ALLOCATE_BUFFER | FORMAT_MESSAGE_FROM_SYSTEM | FORMAT_MESSAGE_IGNORE_INSERTS
+///     let flags = 0x100 | 0x1000 | 0x200;
+///     let mut p = std::ptr::null_mut();
+///     let n = unsafe {
+///         FormatMessageW(flags, std::ptr::null(), 5, 0, &mut p, 0, std::ptr::null_mut())
+///     };
+///     assert!(n > 0);
+///     let message = unsafe { LocalWCString::from_raw(p) }.unwrap();
+///     assert!(message.len() == n as usize);
+///     # }
+pub type LocalWCString = ForeignWCString<LocalMem>;
+
+unsafe impl<D: Deleter> Send for ForeignWCString<D> {}
+unsafe impl<D: Deleter> Sync for ForeignWCString<D> {}
+
+impl<D: Deleter> ForeignWCString<D> {
+    /// Take ownership of a ```nul``` terminated string allocated with the allocator matching ```D```,
+    /// returning ```None``` if ```ptr``` is null.
+    ///
+    /// * If ```ptr``` is non-null, it must point to a ```nul``` terminated string that is not owned elsewhere.
+    pub unsafe fn from_raw(ptr: *mut u16) -> Option<ForeignWCString<D>> {
+        if ptr.is_null() {
+            return None;
+        }
+        let len = WCStr::from_ptr(ptr).len();
+        Some(ForeignWCString {
+            ptr: ptr,
+            len: len,
+            deleter: PhantomData,
+        })
+    }
+
+    /// Release ownership of the string, which must then be freed by the caller.
+    pub fn into_raw(self) -> *mut u16 {
+        let ptr = self.ptr;
+        std::mem::forget(self);
+        ptr
+    }
+
+    /// Return the string as a ```&WCStr```.
+    pub fn as_wcstr(&self) -> &WCStr {
+        unsafe { wcstr::from_units_with_nul(std::slice::from_raw_parts(self.ptr, self.len + 1)) }
+    }
+}
+
+impl<D: Deleter> Drop for ForeignWCString<D> {
+    fn drop(&mut self) {
+        unsafe { D::free(self.ptr) };
+    }
+}
+
+impl<D: Deleter> Deref for ForeignWCString<D> {
+    type Target = WCStr;
+    fn deref(&self) -> &WCStr {
+        self.as_wcstr()
+    }
+}
+
+impl<D: Deleter> AsRef<WCStr> for ForeignWCString<D> {
+    fn as_ref(&self) -> &WCStr {
+        self.as_wcstr()
+    }
+}
+
+impl<D: Deleter> Borrow<WCStr> for ForeignWCString<D> {
+    fn borrow(&self) -> &WCStr {
+        self.as_wcstr()
+    }
+}
+
+impl<D: Deleter> std::fmt::Debug for ForeignWCString<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self.as_wcstr(), f)
+    }
+}
+
+impl<D: Deleter> std::fmt::Display for ForeignWCString<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        std::fmt::Display::fmt(self.as_wcstr(), f)
+    }
+}
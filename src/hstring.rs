@@ -0,0 +1,208 @@
+
+use ::std;
+use ::std::os::raw::c_void;
+
+use ::{WCStr, WCString, NulError};
+use ::error;
+use ::wcstr;
+
+#[allow(non_snake_case)]
+#[link(name = "runtimeobject")]
+extern "system" {
+    fn WindowsCreateString(sourceString: *const u16, length: u32, string: *mut *mut c_void) -> i32;
+    fn WindowsDuplicateString(string: *mut c_void, newString: *mut *mut c_void) -> i32;
+    fn WindowsDeleteString(string: *mut c_void) -> i32;
+    fn WindowsGetStringRawBuffer(string: *mut c_void, length: *mut u32) -> *const u16;
+}
+
+/// Representation of an owned WinRT ```HSTRING```, created with ```WindowsCreateString``` and released with
+/// ```WindowsDeleteString```. ```HSTRING```s are immutable and reference counted, so ```clone()``` is cheap.
+///
+/// A null ```HSTRING``` is a valid, empty string.
+///
+/// # ```HString``` example
+///     use wcstr::{HString, WCString};
+///     let s = WCString::from_str("Windows.Foundation.Uri").unwrap();
+///     let h = s.to_hstring();
+///     assert!(h.len() == 22);
+///     assert!(h.as_slice() == s.as_slice());
+///     assert!(h.as_wcstr().unwrap() == s.as_wcstr());
+///     assert!(h.clone().to_wcstring().unwrap() == s);
+///     assert!(HString::new().as_raw().is_null());
+pub struct HString {
+    handle: *mut c_void,
+}
+
+unsafe impl Send for HString {}
+unsafe impl Sync for HString {}
+
+impl HString {
+    /// Create a new, empty (null) ```HSTRING```.
+    pub fn new() -> HString {
+        HString {
+            handle: std::ptr::null_mut(),
+        }
+    }
+
+    /// Create an ```HSTRING``` by copying a slice of ```u16```s, which may contain ```nul```s.
+    ///
+    /// * This will panic if ```WindowsCreateString``` fails, or if the slice is longer than ```u32::MAX```.
+    pub fn from_slice(slice: &[u16]) -> HString {
+        assert!(slice.len() <= u32::MAX as usize, "string too long for an HSTRING");
+        let mut handle = std::ptr::null_mut();
+        let hr = unsafe { WindowsCreateString(slice.as_ptr(), slice.len() as u32, &mut handle) };
+        assert!(hr >= 0, "WindowsCreateString failed with 0x{:08X}", hr);
+        HString {
+            handle: handle,
+        }
+    }
+
+    /// Take ownership of an ```HSTRING```, which will be released with ```WindowsDeleteString```.
+    ///
+    /// * ```handle``` must be null or a valid ```HSTRING``` that is not owned elsewhere.
+    pub unsafe fn from_raw(handle: *mut c_void) -> HString {
+        HString {
+            handle: handle,
+        }
+    }
+
+    /// Release ownership of the ```HSTRING```, typically to return it from a WinRT method.
+    pub fn into_raw(self) -> *mut c_void {
+        let handle = self.handle;
+        std::mem::forget(self);
+        handle
+    }
+
+    /// Return the ```HSTRING``` handle, still owned by this ```HString```.
+    pub fn as_raw(&self) -> *mut c_void {
+        self.handle
+    }
+
+    /// Return the string as a slice of ```u16```s, including the ```nul``` terminator, using
+    /// ```WindowsGetStringRawBuffer```.
+    pub fn as_slice_with_nul(&self) -> &[u16] {
+        let mut len = 0;
+        unsafe {
+            let p = WindowsGetStringRawBuffer(self.handle, &mut len);
+            std::slice::from_raw_parts(p, len as usize + 1)
+        }
+    }
+
+    /// Return the string as a slice of ```u16```s, without the ```nul``` terminator.
+    pub fn as_slice(&self) -> &[u16] {
+        let s = self.as_slice_with_nul();
+        &s[..s.len() - 1]
+    }
+
+    /// Return the length in ```u16```s, not including the ```nul``` terminator.
+    pub fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    /// is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the string as a ```&WCStr```, or ```NulError``` if it contains an embedded ```nul```.
+    pub fn as_wcstr(&self) -> Result<&WCStr, NulError> {
+        let s = self.as_slice_with_nul();
+        match s[..s.len() - 1].iter().position(|&w| w == 0) {
+            Some(i) => Err(error::nul(i, None)),
+            None => Ok(unsafe { wcstr::from_units_with_nul(s) }),
+        }
+    }
+
+    /// Copy the string to a ```WCString```, or return ```NulError``` if it contains an embedded ```nul```.
+    pub fn to_wcstring(&self) -> Result<WCString, NulError> {
+        self.as_wcstr().map(|s| s.to_owned())
+    }
+
+    /// Decode the string to a ```String```, replacing invalid UTF-16 with ```U+FFFD REPLACEMENT CHARACTER```.
+    pub fn to_string_lossy(&self) -> String {
+        String::from_utf16_lossy(self.as_slice())
+    }
+}
+
+impl Drop for HString {
+    fn drop(&mut self) {
+        unsafe { WindowsDeleteString(self.handle) };
+    }
+}
+
+impl Clone for HString {
+    fn clone(&self) -> HString {
+        let mut handle = std::ptr::null_mut();
+        let hr = unsafe { WindowsDuplicateString(self.handle, &mut handle) };
+        assert!(hr >= 0, "WindowsDuplicateString failed with 0x{:08X}", hr);
+        HString {
+            handle: handle,
+        }
+    }
+}
+
+impl Default for HString {
+    fn default() -> HString {
+        HString::new()
+    }
+}
+
+impl PartialEq for HString {
+    fn eq(&self, other: &HString) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl Eq for HString {}
+
+impl std::hash::Hash for HString {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+
+impl std::fmt::Debug for HString {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        std::fmt::Debug::fmt(&self.to_string_lossy(), f)
+    }
+}
+
+/// Same as ```HString::from_slice()```, so it panics if the string is longer than ```u32::MAX```.
+impl<'a> From<&'a WCStr> for HString {
+    fn from(s: &'a WCStr) -> HString {
+        HString::from_slice(s.to_slice())
+    }
+}
+
+impl From<WCString> for HString {
+    fn from(s: WCString) -> HString {
+        HString::from_slice(s.as_slice())
+    }
+}
+
+impl<'a> From<&'a WCString> for HString {
+    fn from(s: &'a WCString) -> HString {
+        HString::from_slice(s.as_slice())
+    }
+}
+
+impl<'a> std::convert::TryFrom<&'a HString> for WCString {
+    type Error = NulError;
+    fn try_from(s: &'a HString) -> Result<WCString, NulError> {
+        s.to_wcstring()
+    }
+}
+
+impl std::convert::TryFrom<HString> for WCString {
+    type Error = NulError;
+    fn try_from(s: HString) -> Result<WCString, NulError> {
+        s.to_wcstring()
+    }
+}
+
+impl WCStr {
+    /// Copy this "wide" string to a new ```HSTRING```, see ```HString```.
+    pub fn to_hstring(&self) -> HString {
+        HString::from(self)
+    }
+}
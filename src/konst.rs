@@ -0,0 +1,99 @@
+
+//! ```const fn``` helpers used by the macros.
+
+/// Return the number of ```u16```s needed to encode ```s``` as UTF-16.
+pub const fn utf16_len(s: &str) -> usize {
+    let b = s.as_bytes();
+    let mut i = 0;
+    let mut n = 0;
+    while i < b.len() {
+        let x = b[i];
+        if x < 0x80 {
+            i += 1;
+            n += 1;
+        }
+        else if x < 0xE0 {
+            i += 2;
+            n += 1;
+        }
+        else if x < 0xF0 {
+            i += 3;
+            n += 1;
+        }
+        else {
+            i += 4;
+            n += 2;
+        }
+    }
+    n
+}
+
+/// Encode ```s``` as UTF-16 into an array of exactly ```utf16_len(s)``` ```u16```s.
+/// Panics (i.e. fails to compile in a ```const``` context) if ```s``` contains a ```nul```.
+pub const fn encode_str<const N: usize>(s: &str) -> [u16; N] {
+    let b = s.as_bytes();
+    let mut out = [0u16; N];
+    let mut i = 0;
+    let mut j = 0;
+    while i < b.len() {
+        let x = b[i] as u32;
+        let (c, len) = if x < 0x80 {
+            (x, 1)
+        }
+        else if x < 0xE0 {
+            (((x & 0x1F) << 6) | (b[i + 1] as u32 & 0x3F), 2)
+        }
+        else if x < 0xF0 {
+            (((x & 0x0F) << 12) | ((b[i + 1] as u32 & 0x3F) << 6) | (b[i + 2] as u32 & 0x3F), 3)
+        }
+        else {
+            (((x & 0x07) << 18) | ((b[i + 1] as u32 & 0x3F) << 12) | ((b[i + 2] as u32 & 0x3F) << 6) | (b[i + 3] as u32 & 0x3F), 4)
+        };
+        if c == 0 {
+            panic!("nul found in wide string literal");
+        }
+        if c >= 0x10000 {
+            let c = c - 0x10000;
+            out[j] = 0xD800 | (c >> 10) as u16;
+            out[j + 1] = 0xDC00 | (c & 0x3FF) as u16;
+            j += 2;
+        }
+        else {
+            out[j] = c as u16;
+            j += 1;
+        }
+        i += len;
+    }
+    out
+}
+
+/// Return the total length of ```pieces```.
+pub const fn total_len(pieces: &[&[u16]]) -> usize {
+    let mut n = 0;
+    let mut i = 0;
+    while i < pieces.len() {
+        n += pieces[i].len();
+        i += 1;
+    }
+    n
+}
+
+/// Concatenate ```pieces``` into an array of exactly ```total_len(pieces) + 1``` ```u16```s, the last of
+/// which is the ```nul``` terminator.
+pub const fn concat<const N: usize>(pieces: &[&[u16]]) -> [u16; N] {
+    let mut out = [0u16; N];
+    let mut j = 0;
+    let mut i = 0;
+    while i < pieces.len() {
+        let piece = pieces[i];
+        let mut k = 0;
+        while k < piece.len() {
+            out[j] = piece[k];
+            j += 1;
+            k += 1;
+        }
+        i += 1;
+    }
+    assert!(j + 1 == N);
+    out
+}
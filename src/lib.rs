@@ -7,16 +7,145 @@
         unstable_features,
         unused_import_braces,
         unused_qualifications)]
+// The crate keeps its 2015-style field initializers and lifetimes, documents the invariants of its unsafe
+// functions in prose, and its inherent ```from_str``` predates (and differs from) ```FromStr```.
+#![allow(clippy::redundant_field_names,
+         clippy::needless_lifetimes,
+         clippy::missing_safety_doc,
+         clippy::should_implement_trait)]
 
 //! Rust FFI helpers for working with win32 API's "Unicode" functions that uses "wide" strings.
 
 
+#[cfg(feature = "tracing")]
+extern crate tracing;
+#[cfg(feature = "log")]
+extern crate log;
+#[cfg(feature = "unicode-normalization")]
+extern crate unicode_normalization;
+#[cfg(feature = "schemars")]
+extern crate schemars;
+#[cfg(feature = "wcstr-macros")]
+extern crate wcstr_macros;
+#[cfg(feature = "serde")]
+extern crate serde as serde_crate;
+#[cfg(feature = "widestring")]
+extern crate widestring;
+#[cfg(all(windows, feature = "windows-strings"))]
+extern crate windows_strings;
+#[cfg(feature = "windows-sys")]
+extern crate windows_sys;
+#[cfg(all(windows, feature = "winapi"))]
+extern crate winapi;
+
+#[macro_use]
+mod macros;
+mod konst;
 mod wcstr;
 mod wcstring;
 mod split;
+mod chars;
+mod cmdline;
+mod secret;
+mod display;
+mod cmp;
+mod collections;
+mod trie;
+mod env;
+mod cursor;
+mod buffer;
+mod multi;
+mod unicode_string;
+mod path;
+mod bytes;
+mod stream;
+#[cfg(feature = "unicode-normalization")]
+mod normalization;
+#[cfg(feature = "schemars")]
+mod schema;
+#[cfg(feature = "serde")]
+mod serialize;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "widestring")]
+mod u16cstr;
+#[cfg(any(all(windows, feature = "windows-strings"), feature = "windows-sys"))]
+mod pcwstr;
+#[cfg(all(windows, feature = "winapi"))]
+mod lpcwstr;
+#[cfg(feature = "capi")]
+pub mod capi;
+#[cfg(all(windows, feature = "win32"))]
+mod win32;
+#[cfg(all(windows, feature = "win32"))]
+mod bstr;
+#[cfg(all(windows, feature = "win32"))]
+mod hstring;
+#[cfg(all(windows, feature = "win32"))]
+mod foreign;
+#[cfg(any(all(windows, feature = "win32"), feature = "codepage"))]
+mod ansi;
+#[cfg(all(feature = "codepage", not(all(windows, feature = "win32"))))]
+mod codepage;
+#[cfg(feature = "io")]
+mod file;
 mod error;
+mod os;
+mod utf16;
+mod upcase;
+mod search;
+
+pub use error::{NulError, NoNulError, Utf16Error, Utf16ErrorKind, ToStringError, Utf32Error, BufferTooSmallError, CursorError, EnvVarError, EnvVarErrorKind,
+                FromBytesError, TooLongError};
+pub use wcstr::{WCStr, AsOptPtr, TruncatePolicy};
+pub use wcstring::{WCString, NulPolicy};
+pub use split::{Split, SplitOwned};
+pub use chars::{Chars, CharsLossy, CharIndices, CodeUnits};
+pub use cmdline::CommandLineArgs;
+pub use secret::SecretWCString;
+pub use display::{RedactPolicy, RedactedDisplay, WCDisplay};
+pub use cmp::{WCStrOrdering, Ordinal, OrdinalIgnoreCase, Natural};
+pub use collections::{WCStrMap, WCStrSet, WCStrMapIter, WCStrSetIter};
+pub use trie::WCStrTrie;
+pub use env::{EnvMap, EnvironmentBlock};
+pub use cursor::WCStrCursor;
+pub use buffer::WCBuffer;
+pub use multi::{WCMultiStr, WCMultiString, WCMultiStrIter};
+pub use unicode_string::UnicodeString;
+pub use stream::{Utf16Decoder, Utf16Encoder};
+#[cfg(any(all(windows, feature = "win32"), feature = "codepage"))]
+pub use ansi::AnsiPolicy;
+#[cfg(all(windows, feature = "win32"))]
+pub use bstr::{BStr, BString};
+#[cfg(all(windows, feature = "win32"))]
+pub use hstring::HString;
+#[cfg(all(windows, feature = "win32"))]
+pub use foreign::{ForeignWCString, Deleter, CoTaskMem, LocalMem, GlobalMem, ProcessHeap, CoTaskWCString, LocalWCString};
+#[cfg(feature = "io")]
+pub use file::read_wcstring;
+#[cfg(feature = "unicode-normalization")]
+pub use normalization::NormalizationForm;
+/// Expand a string literal to a ```&'static WCStr``` at compile time, for class names, registry paths and
+/// other constant strings. Requires the ```wcstr-macros``` feature.
+///
+/// * The UTF-16 encoding and ```nul``` terminator are computed at compile time.
+/// * A ```nul``` in the literal is a compile time error.
+/// * The result can be used to initialize ```const``` and ```static``` items.
+///
+/// # ```wcstr!()``` example
+///
+///     #[macro_use] extern crate wcstr;
+///     use wcstr::WCStr;
+///     const CLASS_NAME: &'static WCStr = wcstr!("MyWindowClass");
+///     # fn main() {
+///     assert!(*CLASS_NAME == *"MyWindowClass");
+///     assert!(wcstr!("\u{1F600}").len() == 2);
+///     # }
+#[cfg(feature = "wcstr-macros")]
+pub use wcstr_macros::wcstr;
 
-pub use error::{NulError, NoNulError};
-pub use wcstr::WCStr;
-pub use wcstring::WCString;
-pub use split::Split;
+#[doc(hidden)]
+pub mod __private {
+    pub use konst::{utf16_len, encode_str, total_len, concat};
+    pub use wcstr::{units, from_units_with_nul};
+}
@@ -0,0 +1,60 @@
+
+//! Accessors for the string pointer aliases of the ```winapi``` crate.
+
+use ::winapi::um::winnt::{LPCWSTR, LPWSTR};
+
+use ::{WCStr, WCBuffer};
+#[cfg(not(feature = "windows-sys"))]
+use ::WCString;
+
+impl WCStr {
+    /// Return a ```LPCWSTR``` (as used by the ```winapi``` crate) to this "wide" string.
+    ///
+    /// # ```as_lpcwstr()``` example
+    ///     # extern crate winapi;
+    ///     # extern crate wcstr;
+    ///     # fn main() {
+    ///     use winapi::um::winnt::LPCWSTR;
+    ///     use wcstr::WCString;
+    ///     let s = WCString::from_str("Notepad").unwrap();
+    ///     let p: LPCWSTR = s.as_lpcwstr();
+    ///     assert!(p == s.as_ptr());
+    ///     let p: LPCWSTR = (&s).into();
+    ///     assert!(p == s.as_ptr());
+    ///     # }
+    pub fn as_lpcwstr(&self) -> LPCWSTR {
+        self.as_ptr()
+    }
+}
+
+impl WCBuffer {
+    /// Return a ```LPWSTR``` (as used by the ```winapi``` crate) to the buffer, valid for writes of
+    /// ```capacity()``` ```u16```s.
+    pub fn as_lpwstr(&mut self) -> LPWSTR {
+        self.as_mut_ptr()
+    }
+}
+
+// With ```windows-sys```, these are already implemented for its ```PCWSTR```/```PWSTR```, which are the
+// same pointer types.
+
+#[cfg(not(feature = "windows-sys"))]
+impl<'a> From<&'a WCStr> for LPCWSTR {
+    fn from(s: &'a WCStr) -> LPCWSTR {
+        s.as_lpcwstr()
+    }
+}
+
+#[cfg(not(feature = "windows-sys"))]
+impl<'a> From<&'a WCString> for LPCWSTR {
+    fn from(s: &'a WCString) -> LPCWSTR {
+        s.as_lpcwstr()
+    }
+}
+
+#[cfg(not(feature = "windows-sys"))]
+impl<'a> From<&'a mut WCBuffer> for LPWSTR {
+    fn from(buffer: &'a mut WCBuffer) -> LPWSTR {
+        buffer.as_lpwstr()
+    }
+}
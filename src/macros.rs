@@ -0,0 +1,74 @@
+
+/// Concatenate string literals and ```const``` ```&'static WCStr``` values into a single
+/// ```&'static WCStr``` at compile time.
+///
+/// * String literals are encoded to UTF-16 at compile time.
+/// * Any other piece must be a ```const```-evaluable expression of type ```&WCStr```.
+/// * A ```nul``` in any literal is a compile time error.
+///
+/// The result can be used to initialize ```const``` and ```static``` items.
+///
+/// # ```wconcat!()``` example
+///
+///     #[macro_use] extern crate wcstr;
+///     use wcstr::WCStr;
+///     const ROOT: &'static WCStr = wconcat!("C:\\Program Files\\", "Vendor");
+///     const EXE: &'static WCStr = wconcat!("\\\\?\\", ROOT, "\\app.exe");
+///     # fn main() {
+///     assert!(*EXE == *"\\\\?\\C:\\Program Files\\Vendor\\app.exe");
+///     assert!(wconcat!().is_empty());
+///     # }
+#[macro_export]
+macro_rules! wconcat {
+    ($($pieces:tt)*) => {
+        $crate::__wconcat!(@pieces [] $($pieces)*)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __wconcat {
+    (@pieces [$($done:expr,)*]) => {{
+        const PIECES: &'static [&'static [u16]] = &[$($done),*];
+        const UNITS: [u16; $crate::__private::total_len(PIECES) + 1] = $crate::__private::concat(PIECES);
+        unsafe { $crate::__private::from_units_with_nul(&UNITS) }
+    }};
+    (@pieces [$($done:expr,)*] $lit:literal) => {
+        $crate::__wconcat!(@pieces [$($done,)*] $lit,)
+    };
+    (@pieces [$($done:expr,)*] $lit:literal, $($rest:tt)*) => {
+        $crate::__wconcat!(@pieces [$($done,)* {
+            const S: &'static str = $lit;
+            const UNITS: [u16; $crate::__private::utf16_len(S)] = $crate::__private::encode_str(S);
+            &UNITS
+        },] $($rest)*)
+    };
+    (@pieces [$($done:expr,)*] $piece:expr) => {
+        $crate::__wconcat!(@pieces [$($done,)*] $piece,)
+    };
+    (@pieces [$($done:expr,)*] $piece:expr, $($rest:tt)*) => {
+        $crate::__wconcat!(@pieces [$($done,)* $crate::__private::units($piece),] $($rest)*)
+    };
+}
+
+/// Format into a new ```WCString```, like ```format!```, encoding to UTF-16 on the fly.
+///
+/// * This will panic if the formatted text contains ```'\0'```; use ```write!``` on a ```WCString``` to
+///   handle that as an error instead.
+///
+/// # ```wcformat!()``` example
+///
+///     #[macro_use] extern crate wcstr;
+///     # fn main() {
+///     let title = wcformat!("{} ({} files)", "Backup", 3);
+///     assert!(title == "Backup (3 files)");
+///     # }
+#[macro_export]
+macro_rules! wcformat {
+    ($($arg:tt)*) => {{
+        let mut s = $crate::WCString::new();
+        ::std::fmt::Write::write_fmt(&mut s, format_args!($($arg)*))
+            .expect("wcformat!: formatted text contains nul");
+        s
+    }};
+}
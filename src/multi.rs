@@ -0,0 +1,246 @@
+
+use ::std;
+
+use ::{WCStr, WCString, NoNulError};
+use ::error;
+use ::wcstr;
+
+/// Representation of a borrowed list of "wide" strings in a single double-```nul``` terminated block, as used
+/// by ```REG_MULTI_SZ``` values and ```lpstrFilter```: ```"one\0two\0\0"```.
+///
+/// The block can't contain an empty string, since that would end the list.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WCMultiStr {
+    inner: [u16],
+}
+
+/// Representation of an owned list of "wide" strings in a single double-```nul``` terminated block.
+///
+/// # ```WCMultiString``` example
+///     use wcstr::{WCMultiString, WCString};
+///     let filter: WCMultiString = ["Text files", "*.txt", "All files", "*.*"].iter()
+///         .map(|s| WCString::from_str(s).unwrap())
+///         .collect();
+///     assert!(filter.len() == 4);
+///     assert!(filter.as_slice_with_nul().ends_with(&[b'*' as u16, 0, 0]));
+///     let names: Vec<String> = filter.iter().map(|s| s.to_string_lossy()).collect();
+///     assert!(names == ["Text files", "*.txt", "All files", "*.*"]);
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+pub struct WCMultiString {
+    inner: Vec<u16>,
+}
+
+/// Created with method ```.iter()``` on ```WCMultiStr```
+#[derive(Clone, Debug)]
+pub struct WCMultiStrIter<'a> {
+    rest: &'a [u16],
+}
+
+const EMPTY: &[u16] = &[0, 0];
+
+impl WCMultiStr {
+    /// Create a ```&WCMultiStr``` from a slice of ```u16```s, such as ```REG_MULTI_SZ``` data.
+    /// The block ends at the first empty string, so the slice is scanned for two consecutive ```nul```s, or a
+    /// ```nul``` at the start for an empty list. ```NoNulError``` is returned if the block is not terminated.
+    ///
+    /// # ```from_slice_with_nul()``` example
+    ///     use wcstr::WCMultiStr;
+    ///     let data: Vec<u16> = "a\0bc\0\0ignored".encode_utf16().collect();
+    ///     let list = WCMultiStr::from_slice_with_nul(&data).unwrap();
+    ///     assert!(list.len() == 2);
+    ///     assert!(list.as_slice_with_nul().len() == 6);
+    ///     assert!(WCMultiStr::from_slice_with_nul(&data[..4]).is_err());
+    pub fn from_slice_with_nul<'a>(slice: &'a [u16]) -> Result<&'a WCMultiStr, NoNulError> {
+        if slice.first() == Some(&0) {
+            return Ok(WCMultiStr::empty());
+        }
+        match slice.windows(2).position(|w| w == [0, 0]) {
+            Some(i) => Ok(unsafe { WCMultiStr::from_slice_with_nul_unchecked(&slice[..i + 2]) }),
+            None => Err(error::no_nul(None)),
+        }
+    }
+
+    /// Create a ```&WCMultiStr``` from a slice of ```u16```s that ends with two ```nul```s and contains no other
+    /// consecutive ```nul```s, without checking.
+    pub unsafe fn from_slice_with_nul_unchecked<'a>(slice: &'a [u16]) -> &'a WCMultiStr {
+        std::mem::transmute(slice)
+    }
+
+    /// Return an empty ```&'static WCMultiStr```.
+    pub fn empty() -> &'static WCMultiStr {
+        unsafe { WCMultiStr::from_slice_with_nul_unchecked(EMPTY) }
+    }
+
+    /// Return the number of strings in the list.
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// is empty
+    pub fn is_empty(&self) -> bool {
+        self.inner[0] == 0
+    }
+
+    /// Iterate over the strings in the list.
+    pub fn iter<'a>(&'a self) -> WCMultiStrIter<'a> {
+        WCMultiStrIter {
+            rest: &self.inner,
+        }
+    }
+
+    /// Return a raw pointer to the double-```nul``` terminated block.
+    pub fn as_ptr(&self) -> *const u16 {
+        self.inner.as_ptr()
+    }
+
+    /// Return the block as a slice of ```u16```s, including both ```nul```s at the end.
+    pub fn as_slice_with_nul(&self) -> &[u16] {
+        &self.inner
+    }
+}
+
+impl std::fmt::Debug for WCMultiStr {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl ToOwned for WCMultiStr {
+    type Owned = WCMultiString;
+    fn to_owned(&self) -> WCMultiString {
+        WCMultiString {
+            inner: self.inner.to_vec(),
+        }
+    }
+}
+
+impl AsRef<WCMultiStr> for WCMultiStr {
+    fn as_ref(&self) -> &WCMultiStr {
+        self
+    }
+}
+
+impl<'a> IntoIterator for &'a WCMultiStr {
+    type Item = &'a WCStr;
+    type IntoIter = WCMultiStrIter<'a>;
+    fn into_iter(self) -> WCMultiStrIter<'a> {
+        self.iter()
+    }
+}
+
+impl<'a> Iterator for WCMultiStrIter<'a> {
+    type Item = &'a WCStr;
+    fn next(&mut self) -> Option<&'a WCStr> {
+        match self.rest.iter().position(|&w| w == 0) {
+            Some(0) | None => None,
+            Some(i) => {
+                let s = unsafe { wcstr::from_units_with_nul(&self.rest[..i + 1]) };
+                self.rest = &self.rest[i + 1..];
+                Some(s)
+            },
+        }
+    }
+}
+
+impl<'a> std::iter::FusedIterator for WCMultiStrIter<'a> {}
+
+impl WCMultiString {
+    /// Create an empty list.
+    pub fn new() -> WCMultiString {
+        WCMultiString {
+            inner: EMPTY.to_vec(),
+        }
+    }
+
+    /// Append a string to the list.
+    ///
+    /// * This will assert if ```s``` is empty, since an empty string would end the list.
+    pub fn push<T>(&mut self, s: T)
+        where T: AsRef<WCStr> {
+        let s = s.as_ref();
+        assert!(!s.is_empty());
+        if self.is_empty() {
+            self.inner.clear();
+        }
+        else {
+            self.inner.pop();
+        }
+        self.inner.extend_from_slice(s.to_slice_with_nul());
+        self.inner.push(0);
+    }
+
+    /// Return this list as a ```&WCMultiStr```
+    pub fn as_wcmultistr(&self) -> &WCMultiStr {
+        self
+    }
+
+    /// Return the underlying buffer, including both ```nul```s at the end.
+    pub fn into_vec_with_nul(self) -> Vec<u16> {
+        self.inner
+    }
+}
+
+impl Default for WCMultiString {
+    fn default() -> WCMultiString {
+        WCMultiString::new()
+    }
+}
+
+impl std::ops::Deref for WCMultiString {
+    type Target = WCMultiStr;
+    fn deref(&self) -> &WCMultiStr {
+        unsafe { WCMultiStr::from_slice_with_nul_unchecked(&self.inner) }
+    }
+}
+
+impl std::fmt::Debug for WCMultiString {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self.as_wcmultistr(), f)
+    }
+}
+
+impl AsRef<WCMultiStr> for WCMultiString {
+    fn as_ref(&self) -> &WCMultiStr {
+        self
+    }
+}
+
+impl std::borrow::Borrow<WCMultiStr> for WCMultiString {
+    fn borrow(&self) -> &WCMultiStr {
+        self
+    }
+}
+
+impl<T: AsRef<WCStr>> Extend<T> for WCMultiString {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for s in iter {
+            self.push(s);
+        }
+    }
+}
+
+impl<T: AsRef<WCStr>> std::iter::FromIterator<T> for WCMultiString {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> WCMultiString {
+        let mut m = WCMultiString::new();
+        m.extend(iter);
+        m
+    }
+}
+
+impl<'a> IntoIterator for &'a WCMultiString {
+    type Item = &'a WCStr;
+    type IntoIter = WCMultiStrIter<'a>;
+    fn into_iter(self) -> WCMultiStrIter<'a> {
+        self.iter()
+    }
+}
+
+impl From<WCString> for WCMultiString {
+    fn from(s: WCString) -> WCMultiString {
+        let mut m = WCMultiString::new();
+        if !s.is_empty() {
+            m.push(s);
+        }
+        m
+    }
+}
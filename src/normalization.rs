@@ -0,0 +1,57 @@
+
+use ::unicode_normalization::{self, IsNormalized, UnicodeNormalization};
+
+use ::WCStr;
+
+/// Unicode normalization forms.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum NormalizationForm {
+    /// Canonical composition.
+    Nfc,
+    /// Canonical decomposition.
+    Nfd,
+    /// Compatibility composition.
+    Nfkc,
+    /// Compatibility decomposition.
+    Nfkd,
+}
+
+impl WCStr {
+    /// Check whether this "wide" string is already in the normalization form ```form```.
+    ///
+    /// This runs the Unicode quick-check algorithm first, which is enough to answer for most
+    /// strings, and only normalizes the string for comparison when the quick check is inconclusive.
+    /// A string that is not well-formed UTF-16 (i.e. contains unpaired surrogates) is never normalized.
+    ///
+    /// # ```is_normalized()``` example
+    ///
+    ///     use wcstr::{WCString, NormalizationForm};
+    ///     let composed = WCString::from_str("\u{E9}").unwrap();
+    ///     let decomposed = WCString::from_str("e\u{301}").unwrap();
+    ///     assert!(composed.is_normalized(NormalizationForm::Nfc));
+    ///     assert!(!composed.is_normalized(NormalizationForm::Nfd));
+    ///     assert!(decomposed.is_normalized(NormalizationForm::Nfd));
+    ///     assert!(!decomposed.is_normalized(NormalizationForm::Nfc));
+    pub fn is_normalized(&self, form: NormalizationForm) -> bool {
+        if self.chars().any(|c| c.is_err()) {
+            return false;
+        }
+        let chars = self.chars_lossy();
+        let quick = match form {
+            NormalizationForm::Nfc => unicode_normalization::is_nfc_quick(chars.clone()),
+            NormalizationForm::Nfd => unicode_normalization::is_nfd_quick(chars.clone()),
+            NormalizationForm::Nfkc => unicode_normalization::is_nfkc_quick(chars.clone()),
+            NormalizationForm::Nfkd => unicode_normalization::is_nfkd_quick(chars.clone()),
+        };
+        match quick {
+            IsNormalized::Yes => true,
+            IsNormalized::No => false,
+            IsNormalized::Maybe => match form {
+                NormalizationForm::Nfc => chars.clone().eq(chars.nfc()),
+                NormalizationForm::Nfd => chars.clone().eq(chars.nfd()),
+                NormalizationForm::Nfkc => chars.clone().eq(chars.nfkc()),
+                NormalizationForm::Nfkd => chars.clone().eq(chars.nfkd()),
+            },
+        }
+    }
+}
@@ -0,0 +1,63 @@
+
+//! Conversions between ```OsStr``` and UTF-16.
+//!
+//! On Windows these use the ```OsStrExt```/```OsStringExt``` fast path, which is lossless. Elsewhere the
+//! ```OsStr``` is encoded with ```char::encode_utf16()```, so strings can be prepared for remote Windows
+//! machines on any platform; invalid UTF-8 and unpaired surrogates are replaced with
+//! ```U+FFFD REPLACEMENT CHARACTER```.
+
+use ::std::ffi::{OsStr, OsString};
+
+#[cfg(windows)]
+pub use ::std::os::windows::ffi::EncodeWide;
+
+#[cfg(windows)]
+pub fn encode_wide<'a>(s: &'a OsStr) -> EncodeWide<'a> {
+    use ::std::os::windows::ffi::OsStrExt;
+    s.encode_wide()
+}
+
+#[cfg(windows)]
+pub fn from_wide(w: &[u16]) -> OsString {
+    use ::std::os::windows::ffi::OsStringExt;
+    OsString::from_wide(w)
+}
+
+/// The iterator returned by ```encode_wide()``` on platforms other than Windows.
+#[cfg(not(windows))]
+#[derive(Clone, Debug)]
+pub enum EncodeWide<'a> {
+    Borrowed(::std::str::EncodeUtf16<'a>),
+    Owned(::std::vec::IntoIter<u16>),
+}
+
+#[cfg(not(windows))]
+impl<'a> Iterator for EncodeWide<'a> {
+    type Item = u16;
+    fn next(&mut self) -> Option<u16> {
+        match *self {
+            EncodeWide::Borrowed(ref mut it) => it.next(),
+            EncodeWide::Owned(ref mut it) => it.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match *self {
+            EncodeWide::Borrowed(ref it) => it.size_hint(),
+            EncodeWide::Owned(ref it) => it.size_hint(),
+        }
+    }
+}
+
+#[cfg(not(windows))]
+pub fn encode_wide<'a>(s: &'a OsStr) -> EncodeWide<'a> {
+    match s.to_str() {
+        Some(s) => EncodeWide::Borrowed(s.encode_utf16()),
+        None => EncodeWide::Owned(s.to_string_lossy().encode_utf16().collect::<Vec<u16>>().into_iter()),
+    }
+}
+
+#[cfg(not(windows))]
+pub fn from_wide(w: &[u16]) -> OsString {
+    OsString::from(String::from_utf16_lossy(w))
+}
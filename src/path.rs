@@ -0,0 +1,187 @@
+
+use ::{WCStr, WCString, WCStrCursor};
+
+pub const BACKSLASH: u16 = b'\\' as u16;
+
+pub fn is_separator(w: u16) -> bool {
+    w == BACKSLASH || w == b'/' as u16
+}
+
+// Advance past the drive (C:) or UNC (\\server\share) prefix of a path, if it has one.
+fn skip_prefix(cursor: &mut WCStrCursor) {
+    match (cursor.peek(), cursor.peek_at(1)) {
+        (Some(w), Some(colon)) if colon == b':' as u16 && w < 0x80 && (w as u8).is_ascii_alphabetic() => {
+            cursor.advance();
+            cursor.advance();
+        },
+        (Some(a), Some(b)) if is_separator(a) && is_separator(b) => {
+            cursor.advance();
+            cursor.advance();
+            cursor.skip_while(|w| !is_separator(w));
+            if cursor.advance().is_some() {
+                cursor.skip_while(|w| !is_separator(w));
+            }
+        },
+        _ => {},
+    }
+}
+
+// The length of the drive or UNC prefix of a path, 0 if it has none.
+pub fn prefix_len(s: &WCStr) -> usize {
+    let mut cursor = WCStrCursor::new(s);
+    skip_prefix(&mut cursor);
+    cursor.offset()
+}
+
+// The length of the prefix and root separators of a path.
+fn root_len(s: &WCStr) -> usize {
+    let mut cursor = WCStrCursor::new(s);
+    skip_prefix(&mut cursor);
+    cursor.skip_while(is_separator);
+    cursor.offset()
+}
+
+// The end of a path without its trailing separators, but never before its root.
+fn trim_end(s: &[u16], root: usize) -> usize {
+    root + s[root..].iter().rposition(|&w| !is_separator(w)).map_or(0, |i| i + 1)
+}
+
+impl WCString {
+    /// Append a path component, inserting a single ```\``` unless this path is empty, already ends with a
+    /// separator or is a bare drive like ```C:```.
+    ///
+    /// * If ```path``` starts with a drive or is a UNC path, it replaces this path.
+    /// * If ```path``` starts with a separator, it replaces everything but the drive or UNC prefix of this path.
+    /// * An empty ```path``` leaves this path unchanged.
+    ///
+    /// # ```push_path()``` example
+    ///     use wcstr::WCString;
+    ///     let mut p = WCString::from_str("C:\\Windows").unwrap();
+    ///     p.push_path(WCString::from_str("System32").unwrap());
+    ///     assert!(p == "C:\\Windows\\System32");
+    ///     let mut p = WCString::from_str("C:\\Temp\\").unwrap();
+    ///     p.push_path(WCString::from_str("a.txt").unwrap());
+    ///     assert!(p == "C:\\Temp\\a.txt");
+    ///     p.push_path(WCString::from_str("\\Users").unwrap());
+    ///     assert!(p == "C:\\Users");
+    ///     p.push_path(WCString::from_str("D:\\data").unwrap());
+    ///     assert!(p == "D:\\data");
+    pub fn push_path<T>(&mut self, path: T)
+        where T: AsRef<WCStr> {
+        let path = path.as_ref();
+        if path.is_empty() {
+            return;
+        }
+        if prefix_len(path) > 0 {
+            self.clear();
+        }
+        else if is_separator(path.to_slice()[0]) {
+            let prefix = prefix_len(self);
+            self.truncate(prefix);
+        }
+        else {
+            let s = self.to_slice();
+            let bare_drive = s.len() == 2 && prefix_len(self) == 2;
+            if !s.is_empty() && !is_separator(s[s.len() - 1]) && !bare_drive {
+                self.push_char('\\');
+            }
+        }
+        self.push(path);
+    }
+
+    /// Convert every ```/``` in this path to ```\```.
+    ///
+    /// # ```normalize_separators()``` example
+    ///     use wcstr::WCString;
+    ///     let mut p = WCString::from_str("C:/Program Files/app").unwrap();
+    ///     p.normalize_separators();
+    ///     assert!(p == "C:\\Program Files\\app");
+    pub fn normalize_separators(&mut self) {
+        self.replace(b'/' as u16, BACKSLASH);
+    }
+}
+
+impl WCStr {
+    /// Join a path component to this path, see ```WCString::push_path()```.
+    ///
+    /// # ```join_path()``` example
+    ///     use wcstr::WCString;
+    ///     let dir = WCString::from_str("\\\\server\\share").unwrap();
+    ///     let p = dir.join_path(WCString::from_str("dir\\file").unwrap());
+    ///     assert!(p == "\\\\server\\share\\dir\\file");
+    ///     assert!(dir.join_path(WCString::from_str("\\x").unwrap()) == "\\\\server\\share\\x");
+    pub fn join_path<T>(&self, path: T) -> WCString
+        where T: AsRef<WCStr> {
+        let mut s = self.to_owned();
+        s.push_path(path);
+        s
+    }
+
+    /// Return the last component of this path, ignoring trailing separators.
+    /// Returns ```None``` if the path is empty, is only a root like ```C:\```, or ends in ```..```.
+    ///
+    /// # ```file_name()``` example
+    ///     use wcstr::WCString;
+    ///     let p = WCString::from_str("C:\\Windows\\notepad.exe").unwrap();
+    ///     assert!(p.file_name().unwrap() == WCString::from_str("notepad.exe").unwrap().as_slice());
+    ///     let p = WCString::from_str("C:/Temp/").unwrap();
+    ///     assert!(p.file_name().unwrap() == WCString::from_str("Temp").unwrap().as_slice());
+    ///     assert!(WCString::from_str("C:\\").unwrap().file_name() == None);
+    pub fn file_name(&self) -> Option<&[u16]> {
+        let s = self.to_slice();
+        let root = root_len(self);
+        let end = trim_end(s, root);
+        let start = s[root..end].iter().rposition(|&w| is_separator(w)).map_or(root, |i| root + i + 1);
+        let name = &s[start..end];
+        if name.is_empty() || name == [b'.' as u16, b'.' as u16] {
+            None
+        }
+        else {
+            Some(name)
+        }
+    }
+
+    /// Return the extension of the last component of this path, without the ```.```.
+    /// Returns ```None``` if there is no file name, it has no ```.```, or its only ```.``` is the first unit.
+    ///
+    /// # ```extension()``` example
+    ///     use wcstr::WCString;
+    ///     let p = WCString::from_str("C:\\logs\\app.2024.log").unwrap();
+    ///     assert!(p.extension().unwrap() == WCString::from_str("log").unwrap().as_slice());
+    ///     assert!(WCString::from_str(".gitignore").unwrap().extension() == None);
+    ///     assert!(WCString::from_str("README").unwrap().extension() == None);
+    pub fn extension(&self) -> Option<&[u16]> {
+        let name = self.file_name()?;
+        match name.iter().rposition(|&w| w == b'.' as u16) {
+            Some(0) | None => None,
+            Some(i) => Some(&name[i + 1..]),
+        }
+    }
+
+    /// Return this path without its last component and the separators before it.
+    /// Returns ```None``` if there is no last component, like ```file_name()```. The parent of a path with a
+    /// single relative component is empty.
+    ///
+    /// # ```parent()``` example
+    ///     use wcstr::WCString;
+    ///     let p = WCString::from_str("C:\\Windows\\System32\\").unwrap();
+    ///     assert!(p.parent().unwrap() == &p.as_slice()[..10]);
+    ///     let p = WCString::from_str("C:\\Windows").unwrap();
+    ///     assert!(p.parent().unwrap() == &p.as_slice()[..3]);
+    ///     let p = WCString::from_str("\\\\server\\share\\x").unwrap();
+    ///     assert!(p.parent().unwrap() == &p.as_slice()[..15]);
+    ///     assert!(WCString::from_str("a").unwrap().parent().unwrap().is_empty());
+    ///     assert!(WCString::from_str("\\").unwrap().parent() == None);
+    pub fn parent(&self) -> Option<&[u16]> {
+        let s = self.to_slice();
+        let root = root_len(self);
+        let end = trim_end(s, root);
+        if end == root {
+            return None;
+        }
+        match s[root..end].iter().rposition(|&w| is_separator(w)) {
+            Some(i) => Some(&s[..trim_end(&s[..root + i], root)]),
+            None => Some(&s[..root]),
+        }
+    }
+}
@@ -0,0 +1,151 @@
+
+//! Conversions to the string types of the ```windows``` and ```windows-sys``` crates.
+
+#[cfg(all(windows, feature = "windows-strings"))]
+use ::std;
+#[cfg(all(windows, feature = "windows-strings"))]
+use ::windows_strings::{HSTRING, PCWSTR, PWSTR};
+
+use ::{WCStr, WCString, WCBuffer};
+#[cfg(all(windows, feature = "windows-strings"))]
+use ::NulError;
+
+#[cfg(all(windows, feature = "windows-strings"))]
+impl WCStr {
+    /// Return a ```PCWSTR``` (as used by the ```windows``` crate) to this "wide" string.
+    ///
+    /// # ```as_pcwstr()``` example
+    ///     # extern crate windows_strings;
+    ///     # extern crate wcstr;
+    ///     # fn main() {
+    ///     use windows_strings::PCWSTR;
+    ///     use wcstr::WCString;
+    ///     let s = WCString::from_str("Notepad").unwrap();
+    ///     let p: PCWSTR = s.as_pcwstr();
+    ///     assert!(unsafe { p.as_wide() } == s.as_slice());
+    ///     # }
+    pub fn as_pcwstr(&self) -> PCWSTR {
+        PCWSTR::from_raw(self.as_ptr())
+    }
+
+    /// Create a ```&WCStr``` from a ```PCWSTR```, see ```from_ptr()```.
+    pub unsafe fn from_pcwstr<'a>(p: PCWSTR) -> &'a WCStr {
+        WCStr::from_ptr(p.as_ptr())
+    }
+}
+
+#[cfg(all(windows, feature = "windows-strings"))]
+impl WCBuffer {
+    /// Return a ```PWSTR``` (as used by the ```windows``` crate) to the buffer, valid for writes of
+    /// ```capacity()``` ```u16```s.
+    pub fn as_pwstr(&mut self) -> PWSTR {
+        PWSTR::from_raw(self.as_mut_ptr())
+    }
+}
+
+#[cfg(all(windows, feature = "windows-strings"))]
+impl<'a> From<&'a WCStr> for PCWSTR {
+    fn from(s: &'a WCStr) -> PCWSTR {
+        s.as_pcwstr()
+    }
+}
+
+#[cfg(all(windows, feature = "windows-strings"))]
+impl<'a> From<&'a WCString> for PCWSTR {
+    fn from(s: &'a WCString) -> PCWSTR {
+        s.as_pcwstr()
+    }
+}
+
+#[cfg(all(windows, feature = "windows-strings"))]
+impl<'a> From<&'a mut WCBuffer> for PWSTR {
+    fn from(buffer: &'a mut WCBuffer) -> PWSTR {
+        buffer.as_pwstr()
+    }
+}
+
+/// Copy a ```&WCStr``` to an ```HSTRING``` of the ```windows``` crate, for WinRT projections.
+///
+/// # ```From<&WCStr> for HSTRING``` example
+///     # extern crate windows_strings;
+///     # extern crate wcstr;
+///     # fn main() {
+///     use std::convert::TryFrom;
+///     use windows_strings::HSTRING;
+///     use wcstr::WCString;
+///     let s = WCString::from_str("ms-settings:display").unwrap();
+///     let h = HSTRING::from(s.as_wcstr());
+///     assert!(h == "ms-settings:display");
+///     assert!(WCString::try_from(&h).unwrap() == s);
+///     # }
+#[cfg(all(windows, feature = "windows-strings"))]
+impl<'a> From<&'a WCStr> for HSTRING {
+    fn from(s: &'a WCStr) -> HSTRING {
+        HSTRING::from_wide(s.to_slice())
+    }
+}
+
+#[cfg(all(windows, feature = "windows-strings"))]
+impl<'a> From<&'a WCString> for HSTRING {
+    fn from(s: &'a WCString) -> HSTRING {
+        HSTRING::from_wide(s.as_slice())
+    }
+}
+
+#[cfg(all(windows, feature = "windows-strings"))]
+impl From<WCString> for HSTRING {
+    fn from(s: WCString) -> HSTRING {
+        HSTRING::from_wide(s.as_slice())
+    }
+}
+
+/// Copy an ```HSTRING``` of the ```windows``` crate to a ```WCString```, or return ```NulError``` if it
+/// contains an embedded ```nul```.
+#[cfg(all(windows, feature = "windows-strings"))]
+impl<'a> std::convert::TryFrom<&'a HSTRING> for WCString {
+    type Error = NulError;
+    fn try_from(s: &'a HSTRING) -> Result<WCString, NulError> {
+        WCString::from_vec(&s[..])
+    }
+}
+
+/// Convert a ```&WCStr``` to the ```PCWSTR``` of ```windows-sys```, a ```*const u16```.
+///
+/// # ```From<&WCStr>``` example
+///     # extern crate windows_sys;
+///     # extern crate wcstr;
+///     # fn main() {
+///     use windows_sys::core::{PCWSTR, PWSTR};
+///     use wcstr::{WCBuffer, WCString};
+///     let s = WCString::from_str("Notepad").unwrap();
+///     let p: PCWSTR = s.as_wcstr().into();
+///     assert!(p == s.as_ptr());
+///     let p: PCWSTR = (&s).into();
+///     assert!(p == s.as_ptr());
+///     let mut buffer = WCBuffer::new(260);
+///     let out: PWSTR = (&mut buffer).into();
+///     assert!(out == buffer.as_mut_ptr());
+///     # }
+#[cfg(feature = "windows-sys")]
+impl<'a> From<&'a WCStr> for ::windows_sys::core::PCWSTR {
+    fn from(s: &'a WCStr) -> ::windows_sys::core::PCWSTR {
+        s.as_ptr()
+    }
+}
+
+/// Convert a ```&WCString``` to the ```PCWSTR``` of ```windows-sys```, a ```*const u16```.
+#[cfg(feature = "windows-sys")]
+impl<'a> From<&'a WCString> for ::windows_sys::core::PCWSTR {
+    fn from(s: &'a WCString) -> ::windows_sys::core::PCWSTR {
+        s.as_ptr()
+    }
+}
+
+/// Convert a ```&mut WCBuffer``` to the ```PWSTR``` of ```windows-sys```, a ```*mut u16``` valid for writes of
+/// ```capacity()``` ```u16```s.
+#[cfg(feature = "windows-sys")]
+impl<'a> From<&'a mut WCBuffer> for ::windows_sys::core::PWSTR {
+    fn from(buffer: &'a mut WCBuffer) -> ::windows_sys::core::PWSTR {
+        buffer.as_mut_ptr()
+    }
+}
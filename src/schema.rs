@@ -0,0 +1,69 @@
+
+use ::std::borrow::Cow;
+
+use ::schemars::{JsonSchema, Schema, SchemaGenerator};
+
+use ::{WCStr, WCString};
+
+fn wide_string_schema() -> Schema {
+    ::schemars::json_schema!({
+        "type": "string",
+        "pattern": "^[^\\u0000]*$",
+        "description": "A Win32 \"wide\" (UTF-16) string, represented as a JSON string. It must not contain U+0000. \
+                        Unpaired surrogates cannot be represented in JSON text and are replaced with U+FFFD."
+    })
+}
+
+/// A "wide" string is described as a JSON string that must not contain ```U+0000```.
+///
+/// JSON text cannot carry unpaired surrogates, so strings that are not well-formed UTF-16 can only be
+/// represented lossily.
+impl JsonSchema for WCStr {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        "WCStr".into()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        "wcstr::WCStr".into()
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        wide_string_schema()
+    }
+}
+
+/// A "wide" string is described as a JSON string that must not contain ```U+0000```.
+///
+/// JSON text cannot carry unpaired surrogates, so strings that are not well-formed UTF-16 can only be
+/// represented lossily.
+///
+/// # ```JsonSchema``` example
+///
+///     # extern crate schemars;
+///     # extern crate wcstr;
+///     # fn main() {
+///     use wcstr::WCString;
+///     let schema = schemars::schema_for!(WCString);
+///     assert!(schema.get("type").unwrap() == "string");
+///     # }
+impl JsonSchema for WCString {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        "WCString".into()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        "wcstr::WCString".into()
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        wide_string_schema()
+    }
+}
@@ -0,0 +1,42 @@
+
+/// Return the offset of the first occurrence of ```needle``` in ```haystack```.
+/// An empty ```needle``` matches at 0.
+pub fn find(haystack: &[u16], needle: &[u16]) -> Option<usize> {
+    if needle.is_empty() {
+        return Some(0);
+    }
+    if needle.len() > haystack.len() {
+        return None;
+    }
+    let first = needle[0];
+    (0..haystack.len() - needle.len() + 1)
+        .filter(|&i| haystack[i] == first)
+        .find(|&i| &haystack[i..i + needle.len()] == needle)
+}
+
+/// Same as ```find()```, but takes the units of ```needle``` as an iterator, so a needle that is being encoded
+/// doesn't have to be collected first. ```needle``` is cloned for every candidate offset.
+pub fn find_units<I>(haystack: &[u16], needle: I) -> Option<usize>
+    where I: Iterator<Item = u16> + Clone {
+    let len = needle.clone().count();
+    if len > haystack.len() {
+        return None;
+    }
+    (0..haystack.len() - len + 1).find(|&i| haystack[i..i + len].iter().cloned().eq(needle.clone()))
+}
+
+/// Return the offset of the last occurrence of ```needle``` in ```haystack```.
+/// An empty ```needle``` matches at ```haystack.len()```.
+pub fn rfind(haystack: &[u16], needle: &[u16]) -> Option<usize> {
+    if needle.is_empty() {
+        return Some(haystack.len());
+    }
+    if needle.len() > haystack.len() {
+        return None;
+    }
+    let first = needle[0];
+    (0..haystack.len() - needle.len() + 1)
+        .rev()
+        .filter(|&i| haystack[i] == first)
+        .find(|&i| &haystack[i..i + needle.len()] == needle)
+}
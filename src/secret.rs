@@ -0,0 +1,74 @@
+
+use ::std;
+
+use ::{WCStr, WCString};
+
+/// An owned "wide" string holding sensitive data, such as a password passed to ```LogonUserW```.
+///
+/// * ```Debug``` and ```Display``` print ```[REDACTED]``` instead of the contents.
+/// * The contents can only be reached through the scoped ```expose_secret()``` call.
+/// * The buffer (including any spare capacity) is zeroed when the ```SecretWCString``` is dropped.
+///
+/// Copies made before the string was wrapped (for example by reallocations while it was being
+/// built) are not covered by this.
+pub struct SecretWCString {
+    inner: WCString,
+}
+
+impl SecretWCString {
+    /// Wrap a ```WCString``` holding sensitive data.
+    /// # ```new()``` example
+    ///     use wcstr::{SecretWCString, WCString};
+    ///     let s = SecretWCString::new(WCString::from_str("hunter2").unwrap());
+    ///     assert!(format!("{:?}", s) == "[REDACTED]");
+    pub fn new(s: WCString) -> SecretWCString {
+        SecretWCString {
+            inner: s,
+        }
+    }
+
+    /// Call ```f``` with the secret string, and return whatever ```f``` returns.
+    ///
+    /// The ```&WCStr``` (and any pointer obtained from it) must not escape ```f```.
+    /// # ```expose_secret()``` example
+    ///     use wcstr::{SecretWCString, WCString};
+    ///     let s = SecretWCString::new(WCString::from_str("hunter2").unwrap());
+    ///     let len = s.expose_secret(|w| {
+    ///         let _ptr = w.as_ptr(); // pass to the Win32 API here
+    ///         w.len()
+    ///     });
+    ///     assert!(len == 7);
+    pub fn expose_secret<F, R>(&self, f: F) -> R
+        where F: FnOnce(&WCStr) -> R {
+        f(&self.inner)
+    }
+}
+
+impl From<WCString> for SecretWCString {
+    fn from(s: WCString) -> SecretWCString {
+        SecretWCString::new(s)
+    }
+}
+
+impl Drop for SecretWCString {
+    fn drop(&mut self) {
+        let mut v = std::mem::take(&mut self.inner).into_vec_with_nul();
+        let ptr = v.as_mut_ptr();
+        for i in 0..v.capacity() {
+            unsafe { std::ptr::write_volatile(ptr.add(i), 0u16) };
+        }
+        std::sync::atomic::compiler_fence(std::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+impl std::fmt::Debug for SecretWCString {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "[REDACTED]")
+    }
+}
+
+impl std::fmt::Display for SecretWCString {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "[REDACTED]")
+    }
+}
@@ -0,0 +1,98 @@
+
+//! Alternative serde representations of "wide" strings, for use with ```#[serde(with = "...")]```.
+//!
+//! By default a ```WCString``` is serialized as a string, replacing unpaired surrogates with
+//! ```U+FFFD REPLACEMENT CHARACTER```. Pick another representation per field:
+//!
+//! * ```wcstr::serde::lossy```: the default, a string with unpaired surrogates replaced.
+//! * ```wcstr::serde::strict```: a string, failing to serialize if there are unpaired surrogates.
+//! * ```wcstr::serde::units```: a sequence of ```u16``` code units, which round-trips any "wide" string.
+//!
+//! All of them reject ```nul``` when deserializing.
+//!
+//! # Example
+//!
+//!     # extern crate serde_json;
+//!     # #[macro_use] extern crate serde_derive;
+//!     # extern crate wcstr;
+//!     # fn main() {
+//!     use wcstr::WCString;
+//!     #[derive(Serialize, Deserialize)]
+//!     struct Entry {
+//!         #[serde(with = "wcstr::serde::strict")]
+//!         name: WCString,
+//!         #[serde(with = "wcstr::serde::units")]
+//!         raw: WCString,
+//!     }
+//!     let raw = WCString::from_vec(vec![0x61, 0xD800]).unwrap();
+//!     let e = Entry { name: WCString::from_str("a").unwrap(), raw: raw.clone() };
+//!     let json = serde_json::to_string(&e).unwrap();
+//!     assert!(json == r#"{"name":"a","raw":[97,55296]}"#);
+//!     let e: Entry = serde_json::from_str(&json).unwrap();
+//!     assert!(e.raw == raw);
+//!     let bad = Entry { name: raw.clone(), raw: raw };
+//!     assert!(serde_json::to_string(&bad).is_err());
+//!     # }
+
+/// Serialize as a string, replacing unpaired surrogates with ```U+FFFD REPLACEMENT CHARACTER```.
+pub mod lossy {
+    use ::serde_crate::{Serialize, Serializer, Deserialize, Deserializer};
+
+    use ::{WCStr, WCString};
+
+    /// Serialize a "wide" string as a string.
+    pub fn serialize<T, S>(s: &T, serializer: S) -> Result<S::Ok, S::Error>
+        where T: ?Sized + AsRef<WCStr>, S: Serializer {
+        s.as_ref().serialize(serializer)
+    }
+
+    /// Deserialize a "wide" string from a string without ```nul```.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<WCString, D::Error>
+        where D: Deserializer<'de> {
+        WCString::deserialize(deserializer)
+    }
+}
+
+/// Serialize as a string, failing if there are unpaired surrogates.
+pub mod strict {
+    use ::serde_crate::{Serializer, Deserialize, Deserializer};
+    use ::serde_crate::ser::Error;
+
+    use ::{WCStr, WCString};
+
+    /// Serialize a "wide" string as a string, failing if it is not well-formed UTF-16.
+    pub fn serialize<T, S>(s: &T, serializer: S) -> Result<S::Ok, S::Error>
+        where T: ?Sized + AsRef<WCStr>, S: Serializer {
+        match s.as_ref().to_string_checked() {
+            Ok(s) => serializer.serialize_str(&s),
+            Err(e) => Err(S::Error::custom(e)),
+        }
+    }
+
+    /// Deserialize a "wide" string from a string without ```nul```.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<WCString, D::Error>
+        where D: Deserializer<'de> {
+        WCString::deserialize(deserializer)
+    }
+}
+
+/// Serialize as a sequence of ```u16``` code units, without the ```nul``` terminator.
+pub mod units {
+    use ::serde_crate::{Serializer, Deserialize, Deserializer};
+    use ::serde_crate::de::Error;
+
+    use ::{WCStr, WCString};
+
+    /// Serialize a "wide" string as a sequence of ```u16```s.
+    pub fn serialize<T, S>(s: &T, serializer: S) -> Result<S::Ok, S::Error>
+        where T: ?Sized + AsRef<WCStr>, S: Serializer {
+        serializer.collect_seq(s.as_ref().to_slice())
+    }
+
+    /// Deserialize a "wide" string from a sequence of ```u16```s without ```nul```.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<WCString, D::Error>
+        where D: Deserializer<'de> {
+        let v: Vec<u16> = Vec::deserialize(deserializer)?;
+        WCString::from_vec(v).map_err(D::Error::custom)
+    }
+}
@@ -0,0 +1,69 @@
+
+use ::std::fmt;
+
+use ::serde_crate::{Serialize, Serializer, Deserialize, Deserializer};
+use ::serde_crate::de::{self, Visitor, Unexpected};
+
+use ::{WCStr, WCString};
+
+/// A "wide" string is serialized as a string. Unpaired surrogates are replaced with
+/// ```U+FFFD REPLACEMENT CHARACTER```.
+impl Serialize for WCStr {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer {
+        serializer.collect_str(&self.as_display())
+    }
+}
+
+/// A "wide" string is serialized as a string. Unpaired surrogates are replaced with
+/// ```U+FFFD REPLACEMENT CHARACTER```.
+///
+/// # ```Serialize``` example
+///
+///     # extern crate serde_json;
+///     # extern crate wcstr;
+///     # fn main() {
+///     use wcstr::WCString;
+///     let s = WCString::from_str("C:\\Temp").unwrap();
+///     assert!(serde_json::to_string(&s).unwrap() == r#""C:\\Temp""#);
+///     # }
+impl Serialize for WCString {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer {
+        self.as_wcstr().serialize(serializer)
+    }
+}
+
+struct WCStringVisitor;
+
+impl<'de> Visitor<'de> for WCStringVisitor {
+    type Value = WCString;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a string without nul")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<WCString, E>
+        where E: de::Error {
+        WCString::from_str(v).map_err(|_| E::invalid_value(Unexpected::Str(v), &self))
+    }
+}
+
+/// A "wide" string is deserialized from a string. Strings containing ```nul``` are rejected.
+///
+/// # ```Deserialize``` example
+///
+///     # extern crate serde_json;
+///     # extern crate wcstr;
+///     # fn main() {
+///     use wcstr::WCString;
+///     let s: WCString = serde_json::from_str(r#""C:\\Temp""#).unwrap();
+///     assert!(s == "C:\\Temp");
+///     assert!(serde_json::from_str::<WCString>(r#""a\u0000b""#).is_err());
+///     # }
+impl<'de> Deserialize<'de> for WCString {
+    fn deserialize<D>(deserializer: D) -> Result<WCString, D::Error>
+        where D: Deserializer<'de> {
+        deserializer.deserialize_str(WCStringVisitor)
+    }
+}
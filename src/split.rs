@@ -1,6 +1,6 @@
 
 use ::std;
-use ::WCStr;
+use ::{WCStr, WCString};
 
 /// Created with method ```.split(delim)```
 #[derive(Debug)]
@@ -9,6 +9,15 @@ pub struct Split {
     offset: usize,
 }
 
+/// Created with method ```.split_owned(delim)```
+#[derive(Debug)]
+pub struct SplitOwned {
+    buffer: Vec<u16>,
+    offset: usize,
+    delim: u16,
+    finished: bool,
+}
+
 pub fn new(buffer: Vec<u16>, delim: u16) -> Split {
     let mut buffer = buffer;
     *buffer.last_mut().unwrap() = delim;
@@ -18,6 +27,18 @@ pub fn new(buffer: Vec<u16>, delim: u16) -> Split {
     }
 }
 
+pub fn new_owned(buffer: Vec<u16>, delim: u16) -> SplitOwned {
+    let mut buffer = buffer;
+    let _nul = buffer.pop();
+    debug_assert_eq!(_nul, Some(0u16));
+    SplitOwned {
+        buffer: buffer,
+        offset: 0,
+        delim: delim,
+        finished: false,
+    }
+}
+
 impl Split {
     /// Get iterator.
     pub fn iter(&mut self) -> &mut Split {
@@ -41,7 +62,7 @@ impl<'a> Iterator for &'a mut Split {
             let offset = pos + 1;
             let result = &self.buffer[self.offset .. offset];
             self.offset = offset;
-            Some(unsafe { std::mem::transmute(result) })
+            Some(unsafe { std::mem::transmute::<&[u16], &WCStr>(result) })
         }
         else {
             None
@@ -49,3 +70,24 @@ impl<'a> Iterator for &'a mut Split {
     }
 }
 
+
+impl Iterator for SplitOwned {
+    type Item = WCString;
+    fn next(&mut self) -> Option<WCString> {
+        if self.finished {
+            return None;
+        }
+        let delim = self.delim;
+        let end = match self.buffer[self.offset..].iter().position(|&w| w == delim) {
+            Some(i) => self.offset + i,
+            None => {
+                self.finished = true;
+                self.buffer.len()
+            },
+        };
+        let mut v = Vec::with_capacity(end - self.offset + 1);
+        v.extend_from_slice(&self.buffer[self.offset .. end]);
+        self.offset = end + 1;
+        Some(unsafe { WCString::from_vec_unchecked(v) })
+    }
+}
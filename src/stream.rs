@@ -0,0 +1,159 @@
+
+use ::std;
+use ::utf16;
+use ::{WCString, NulError};
+
+/// An incremental UTF-16 decoder, for text that arrives in chunks, like console or pipe output read in
+/// fixed-size blocks.
+///
+/// A high surrogate at the end of a chunk (and an odd byte, for byte input) is kept until the next chunk,
+/// so characters split across chunks are decoded correctly. Unpaired surrogates are replaced with
+/// ```U+FFFD REPLACEMENT CHARACTER```.
+///
+/// # ```Utf16Decoder``` example
+///     use wcstr::Utf16Decoder;
+///     let units: Vec<u16> = "a\u{1F600}b".encode_utf16().collect();
+///     let mut decoder = Utf16Decoder::new();
+///     let mut s = String::new();
+///     s.push_str(decoder.decode(&units[..2]));
+///     s.push_str(decoder.decode(&units[2..]));
+///     s.push_str(decoder.finish());
+///     assert!(s == "a\u{1F600}b");
+///     let mut decoder = Utf16Decoder::new();
+///     assert!(decoder.decode_bytes_le(&[b'h', 0, b'i']) == "h");
+///     assert!(decoder.decode_bytes_le(&[0, 0x3D, 0xD8]) == "i");
+///     assert!(decoder.finish() == "\u{FFFD}");
+#[derive(Clone, Debug, Default)]
+pub struct Utf16Decoder {
+    pending: Option<u16>,
+    pending_byte: Option<u8>,
+    units: Vec<u16>,
+    buffer: String,
+}
+
+impl Utf16Decoder {
+    /// Create a decoder with nothing pending.
+    pub fn new() -> Utf16Decoder {
+        Utf16Decoder::default()
+    }
+
+    fn push_units(&mut self, units: &[u16]) {
+        if units.is_empty() {
+            return;
+        }
+        let (units, last) = match units.split_last() {
+            Some((&w, rest)) if utf16::is_high_surrogate(w) => (rest, Some(w)),
+            _ => (units, None),
+        };
+        let iter = self.pending.take().into_iter().chain(units.iter().cloned());
+        for c in std::char::decode_utf16(iter) {
+            self.buffer.push(c.unwrap_or(std::char::REPLACEMENT_CHARACTER));
+        }
+        self.pending = last;
+    }
+
+    /// Decode a chunk of ```u16```s, returning the text that is complete so far.
+    pub fn decode<'a>(&'a mut self, units: &[u16]) -> &'a str {
+        self.buffer.clear();
+        self.push_units(units);
+        &self.buffer
+    }
+
+    /// Decode a chunk of UTF-16LE bytes, returning the text that is complete so far.
+    pub fn decode_bytes_le<'a>(&'a mut self, bytes: &[u8]) -> &'a str {
+        self.buffer.clear();
+        let mut units = std::mem::take(&mut self.units);
+        units.clear();
+        let mut bytes = bytes;
+        if let (Some(first), Some((&second, rest))) = (self.pending_byte, bytes.split_first()) {
+            units.push(u16::from_le_bytes([first, second]));
+            self.pending_byte = None;
+            bytes = rest;
+        }
+        for b in bytes.chunks(2) {
+            match *b {
+                [lo, hi] => units.push(u16::from_le_bytes([lo, hi])),
+                [lo] => self.pending_byte = Some(lo),
+                _ => {},
+            }
+        }
+        self.push_units(&units);
+        self.units = units;
+        &self.buffer
+    }
+
+    /// End the input, returning ```U+FFFD``` for a pending high surrogate or odd byte, and reset the decoder.
+    pub fn finish<'a>(&'a mut self) -> &'a str {
+        self.buffer.clear();
+        if self.pending.take().is_some() {
+            self.buffer.push(std::char::REPLACEMENT_CHARACTER);
+        }
+        if self.pending_byte.take().is_some() {
+            self.buffer.push(std::char::REPLACEMENT_CHARACTER);
+        }
+        &self.buffer
+    }
+}
+
+/// An incremental UTF-16 encoder, for building large "wide" buffers from UTF-8 text that arrives in chunks,
+/// without intermediate allocations. It keeps count of the ```u16```s written so far.
+///
+/// # ```Utf16Encoder``` example
+///     use wcstr::{Utf16Encoder, WCString};
+///     let mut encoder = Utf16Encoder::new();
+///     let mut s = WCString::new();
+///     encoder.encode(&mut s, "log: ").unwrap();
+///     encoder.encode(&mut s, "\u{1F600}").unwrap();
+///     assert!(encoder.encode(&mut s, "a\0b").is_err());
+///     assert!(s == "log: \u{1F600}" && encoder.written() == 7);
+///
+///     let mut buffer = [0u16; 4];
+///     let mut encoder = Utf16Encoder::new();
+///     let text = "ab\u{1F600}c";
+///     let (read, written) = encoder.encode_to_slice(&mut buffer, text);
+///     assert!((read, written) == (6, 4));
+///     let (read, written) = encoder.encode_to_slice(&mut buffer, &text[read..]);
+///     assert!((read, written) == (1, 1) && encoder.written() == 5);
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Utf16Encoder {
+    written: usize,
+}
+
+impl Utf16Encoder {
+    /// Create an encoder that has written nothing.
+    pub fn new() -> Utf16Encoder {
+        Utf16Encoder::default()
+    }
+
+    /// Return the number of ```u16```s written so far.
+    pub fn written(&self) -> usize {
+        self.written
+    }
+
+    /// Append a chunk to a ```WCString```, returning the number of ```u16```s written.
+    /// Nothing is appended and ```NulError``` is returned if the chunk contains ```nul```.
+    pub fn encode(&mut self, out: &mut WCString, s: &str) -> Result<usize, NulError> {
+        let len = out.len();
+        out.push_str(s)?;
+        let written = out.len() - len;
+        self.written += written;
+        Ok(written)
+    }
+
+    /// Write as much of a chunk as fits into ```out```, returning the number of bytes of ```s``` that were
+    /// consumed and the number of ```u16```s written. A character is never split: if its surrogate pair does
+    /// not fit, it is left for the next call. ```nul``` is written like any other character.
+    pub fn encode_to_slice(&mut self, out: &mut [u16], s: &str) -> (usize, usize) {
+        let mut read = 0;
+        let mut written = 0;
+        for c in s.chars() {
+            if out.len() - written < c.len_utf16() {
+                break;
+            }
+            written += c.encode_utf16(&mut out[written..]).len();
+            read += c.len_utf8();
+        }
+        self.written += written;
+        (read, written)
+    }
+}
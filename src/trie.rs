@@ -0,0 +1,181 @@
+
+
+use ::WCStr;
+use ::utf16;
+use ::path::is_separator;
+
+/// A prefix tree keyed by "wide" strings, for mapping strings (typically paths) to the value of their
+/// longest matching prefix in ```O(length of the string)```.
+///
+/// * With ```case_insensitive()```, keys are compared after ```to_upper_invariant()```.
+/// * With ```path_aware()```, ```/``` and ```\``` are treated as the same unit, and a prefix only matches
+///   at a path component boundary: ```C:\Win``` does not match ```C:\Windows```, but ```C:\Windows```
+///   matches ```C:\Windows``` and ```C:\Windows\System32```.
+#[derive(Clone, Debug)]
+pub struct WCStrTrie<V> {
+    nodes: Vec<Node<V>>,
+    len: usize,
+    case_insensitive: bool,
+    path_aware: bool,
+}
+
+#[derive(Clone, Debug)]
+struct Node<V> {
+    children: Vec<(u16, usize)>,
+    value: Option<V>,
+}
+
+impl<V> Node<V> {
+    fn new() -> Node<V> {
+        Node {
+            children: Vec::new(),
+            value: None,
+        }
+    }
+
+    fn child(&self, w: u16) -> Result<usize, usize> {
+        self.children.binary_search_by(|c| c.0.cmp(&w))
+    }
+}
+
+impl<V> WCStrTrie<V> {
+    /// Create an empty trie that compares code units exactly.
+    pub fn new() -> WCStrTrie<V> {
+        WCStrTrie {
+            nodes: vec![Node::new()],
+            len: 0,
+            case_insensitive: false,
+            path_aware: false,
+        }
+    }
+
+    /// Make an empty trie compare keys case-insensitively, like ```OrdinalIgnoreCase```.
+    ///
+    /// * This will assert if the trie is not empty.
+    pub fn case_insensitive(mut self) -> WCStrTrie<V> {
+        assert!(self.is_empty());
+        self.case_insensitive = true;
+        self
+    }
+
+    /// Make an empty trie match prefixes only at path component boundaries.
+    ///
+    /// * This will assert if the trie is not empty.
+    pub fn path_aware(mut self) -> WCStrTrie<V> {
+        assert!(self.is_empty());
+        self.path_aware = true;
+        self
+    }
+
+    fn fold(&self, w: u16) -> u16 {
+        if self.path_aware && w == b'/' as u16 {
+            b'\\' as u16
+        }
+        else if self.case_insensitive {
+            utf16::to_upper_invariant(w)
+        }
+        else {
+            w
+        }
+    }
+
+    /// Insert a value for a key, returning the previous value of an equal key.
+    pub fn insert<T>(&mut self, key: T, value: V) -> Option<V>
+        where T: AsRef<WCStr> {
+        let mut node = 0;
+        for &w in key.as_ref().to_slice() {
+            let w = self.fold(w);
+            node = match self.nodes[node].child(w) {
+                Ok(i) => self.nodes[node].children[i].1,
+                Err(i) => {
+                    let child = self.nodes.len();
+                    self.nodes.push(Node::new());
+                    self.nodes[node].children.insert(i, (w, child));
+                    child
+                },
+            };
+        }
+        let old = self.nodes[node].value.replace(value);
+        if old.is_none() {
+            self.len += 1;
+        }
+        old
+    }
+
+    /// Return the value of a key, if present.
+    pub fn get<T>(&self, key: T) -> Option<&V>
+        where T: AsRef<WCStr> {
+        let mut node = 0;
+        for &w in key.as_ref().to_slice() {
+            match self.nodes[node].child(self.fold(w)) {
+                Ok(i) => node = self.nodes[node].children[i].1,
+                Err(_) => return None,
+            }
+        }
+        self.nodes[node].value.as_ref()
+    }
+
+    /// Find the longest key that is a prefix of ```s```, returning its length in ```u16``` units and its value.
+    ///
+    /// # ```longest_prefix_match()``` example
+    ///     use wcstr::{WCStrTrie, WCString};
+    ///     let mut rules = WCStrTrie::new().case_insensitive().path_aware();
+    ///     rules.insert(WCString::from_str("C:\\Windows").unwrap(), "system");
+    ///     rules.insert(WCString::from_str("C:\\Windows\\Temp\\").unwrap(), "temp");
+    ///     let path = WCString::from_str("c:/windows/temp/x.log").unwrap();
+    ///     assert!(rules.longest_prefix_match(&path) == Some((16, &"temp")));
+    ///     let path = WCString::from_str("C:\\Windows\\notepad.exe").unwrap();
+    ///     assert!(rules.longest_prefix_match(&path) == Some((10, &"system")));
+    ///     let path = WCString::from_str("C:\\WindowsApps").unwrap();
+    ///     assert!(rules.longest_prefix_match(&path) == None);
+    pub fn longest_prefix_match<T>(&self, s: T) -> Option<(usize, &V)>
+        where T: AsRef<WCStr> {
+        let s = s.as_ref().to_slice();
+        let mut node = 0;
+        let mut best = self.match_at(node, s, 0);
+        for (depth, &w) in s.iter().enumerate() {
+            match self.nodes[node].child(self.fold(w)) {
+                Ok(i) => node = self.nodes[node].children[i].1,
+                Err(_) => break,
+            }
+            if let Some(m) = self.match_at(node, s, depth + 1) {
+                best = Some(m);
+            }
+        }
+        best
+    }
+
+    fn match_at(&self, node: usize, s: &[u16], len: usize) -> Option<(usize, &V)> {
+        let value = match self.nodes[node].value {
+            Some(ref value) => value,
+            None => return None,
+        };
+        let at_boundary = !self.path_aware
+            || len == 0
+            || len == s.len()
+            || is_separator(s[len])
+            || is_separator(s[len - 1]);
+        if at_boundary {
+            Some((len, value))
+        }
+        else {
+            None
+        }
+    }
+
+    /// Return the number of keys.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<V> Default for WCStrTrie<V> {
+    fn default() -> WCStrTrie<V> {
+        WCStrTrie::new()
+    }
+}
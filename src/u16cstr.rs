@@ -0,0 +1,74 @@
+
+use ::widestring::{U16CStr, U16CString};
+
+use ::{WCStr, WCString};
+use ::wcstr;
+
+/// Borrow a ```&WCStr``` as a ```&U16CStr```, without copying.
+///
+/// # ```From<&WCStr>``` example
+///
+///     # extern crate widestring;
+///     # extern crate wcstr;
+///     # fn main() {
+///     use widestring::{U16CStr, U16CString};
+///     use wcstr::{WCStr, WCString};
+///     let s = WCString::from_str("C:\\Temp").unwrap();
+///     let u: &U16CStr = s.as_wcstr().into();
+///     assert!(u.as_ptr() == s.as_ptr());
+///     let w: &WCStr = u.into();
+///     assert!(w == s.as_wcstr());
+///     let owned: U16CString = s.clone().into();
+///     assert!(WCString::from(owned) == s);
+///     # }
+impl<'a> From<&'a WCStr> for &'a U16CStr {
+    fn from(s: &'a WCStr) -> &'a U16CStr {
+        unsafe { U16CStr::from_slice_unchecked(s.to_slice_with_nul()) }
+    }
+}
+
+/// Borrow a ```&U16CStr``` as a ```&WCStr```, without copying.
+impl<'a> From<&'a U16CStr> for &'a WCStr {
+    fn from(s: &'a U16CStr) -> &'a WCStr {
+        unsafe { wcstr::from_units_with_nul(s.as_slice_with_nul()) }
+    }
+}
+
+/// Convert a ```WCString``` to a ```U16CString```, reusing the buffer when it has no spare capacity.
+impl From<WCString> for U16CString {
+    fn from(s: WCString) -> U16CString {
+        unsafe { U16CString::from_vec_unchecked(s.into_vec_with_nul()) }
+    }
+}
+
+/// Convert a ```U16CString``` to a ```WCString```, reusing the buffer.
+impl From<U16CString> for WCString {
+    fn from(s: U16CString) -> WCString {
+        unsafe { WCString::from_vec_with_nul_unchecked(s.into_vec_with_nul()) }
+    }
+}
+
+impl AsRef<U16CStr> for WCStr {
+    fn as_ref(&self) -> &U16CStr {
+        self.into()
+    }
+}
+
+impl AsRef<U16CStr> for WCString {
+    fn as_ref(&self) -> &U16CStr {
+        self.as_wcstr().into()
+    }
+}
+
+/// Lets a ```&U16CStr``` or ```U16CString``` be passed to any function that takes ```AsRef<WCStr>```.
+impl AsRef<WCStr> for U16CStr {
+    fn as_ref(&self) -> &WCStr {
+        self.into()
+    }
+}
+
+impl AsRef<WCStr> for U16CString {
+    fn as_ref(&self) -> &WCStr {
+        self.as_ucstr().into()
+    }
+}
@@ -0,0 +1,160 @@
+
+use ::std;
+use ::std::marker::PhantomData;
+
+use ::{WCStr, WCString, NulError, TooLongError};
+use ::error;
+use ::wcstr;
+
+/// The maximum length of a ```UNICODE_STRING``` in ```u16``` units (```Length``` is in bytes, up to
+/// ```0xFFFE```).
+const MAX_LEN: usize = 0x7FFF;
+
+/// A borrowed NT ```UNICODE_STRING```, with the same layout: ```Length``` and ```MaximumLength``` in bytes,
+/// and a pointer to a buffer that is not necessarily ```nul``` terminated.
+///
+/// A ```&UnicodeString``` can be passed wherever a ```PCUNICODE_STRING``` is expected.
+///
+/// # ```UnicodeString``` example
+///     use wcstr::{UnicodeString, WCString};
+///     let s = WCString::from_str("\\??\\C:\\Windows").unwrap();
+///     let u = UnicodeString::new(&s).unwrap();
+///     assert!(u.length() == 28 && u.maximum_length() == 30);
+///     assert!(u.as_slice() == s.as_slice());
+///     assert!(u.as_wcstr() == Some(s.as_wcstr()));
+///     // A counted string without a terminator, such as a part of a path.
+///     let u = UnicodeString::from_slice(&s.as_slice()[4..6]).unwrap();
+///     assert!(u.length() == 4 && u.maximum_length() == 4);
+///     assert!(u.as_wcstr() == None);
+///     assert!(u.to_wcstring().unwrap() == "C:");
+///     let long = vec![b'a' as u16; 0x8000];
+///     assert!(UnicodeString::from_slice(&long).unwrap_err().max_length() == 0x7FFF);
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct UnicodeString<'a> {
+    length: u16,
+    maximum_length: u16,
+    buffer: *const u16,
+    marker: PhantomData<&'a [u16]>,
+}
+
+impl<'a> UnicodeString<'a> {
+    /// Create a ```UnicodeString``` for a "wide" string, with ```MaximumLength``` covering the ```nul```
+    /// terminator when it fits. ```TooLongError``` is returned if the string is longer than ```0x7FFF``` units.
+    pub fn new<T>(s: &'a T) -> Result<UnicodeString<'a>, TooLongError>
+        where T: AsRef<WCStr> + ?Sized {
+        let s = s.as_ref();
+        let mut u = UnicodeString::from_slice(s.to_slice())?;
+        if s.len() < MAX_LEN {
+            u.maximum_length += 2;
+        }
+        Ok(u)
+    }
+
+    /// Create a ```UnicodeString``` for a slice of ```u16```s, which doesn't need to be ```nul``` terminated.
+    /// ```TooLongError``` is returned if the slice is longer than ```0x7FFF``` units.
+    pub fn from_slice(slice: &'a [u16]) -> Result<UnicodeString<'a>, TooLongError> {
+        if slice.len() > MAX_LEN {
+            return Err(error::too_long(slice.len(), MAX_LEN));
+        }
+        let bytes = (slice.len() * 2) as u16;
+        Ok(UnicodeString {
+            length: bytes,
+            maximum_length: bytes,
+            buffer: slice.as_ptr(),
+            marker: PhantomData,
+        })
+    }
+
+    /// Create a ```UnicodeString``` from its fields, such as a ```UNICODE_STRING``` returned by a native API.
+    ///
+    /// * ```buffer``` must be valid for reads of ```maximum_length``` bytes for ```'a```, or null if
+    ///   ```maximum_length``` is 0, and ```length``` must not be greater than ```maximum_length```.
+    pub unsafe fn from_raw_parts(length: u16, maximum_length: u16, buffer: *const u16) -> UnicodeString<'a> {
+        UnicodeString {
+            length: length,
+            maximum_length: maximum_length,
+            buffer: buffer,
+            marker: PhantomData,
+        }
+    }
+
+    /// Return ```Length```, the length of the string in bytes.
+    pub fn length(&self) -> u16 {
+        self.length
+    }
+
+    /// Return ```MaximumLength```, the size of the buffer in bytes.
+    pub fn maximum_length(&self) -> u16 {
+        self.maximum_length
+    }
+
+    /// Return the buffer pointer.
+    pub fn buffer(&self) -> *const u16 {
+        self.buffer
+    }
+
+    /// Return the length in ```u16``` units.
+    pub fn len(&self) -> usize {
+        self.length as usize / 2
+    }
+
+    /// is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the string as a slice of ```u16```s.
+    pub fn as_slice(&self) -> &'a [u16] {
+        if self.buffer.is_null() {
+            return &[];
+        }
+        unsafe { std::slice::from_raw_parts(self.buffer, self.len()) }
+    }
+
+    /// Return the string as a ```&WCStr``` without copying, if the buffer has a ```nul``` terminator right
+    /// after the string and the string contains no ```nul```s.
+    pub fn as_wcstr(&self) -> Option<&'a WCStr> {
+        if self.buffer.is_null() || (self.maximum_length as usize / 2) <= self.len() {
+            return None;
+        }
+        let slice = unsafe { std::slice::from_raw_parts(self.buffer, self.len() + 1) };
+        match slice.iter().position(|&w| w == 0) {
+            Some(i) if i == self.len() => Some(unsafe { wcstr::from_units_with_nul(slice) }),
+            _ => None,
+        }
+    }
+
+    /// Copy the string to a ```WCString```, adding a ```nul``` terminator, or return ```NulError``` if it
+    /// contains a ```nul```.
+    pub fn to_wcstring(&self) -> Result<WCString, NulError> {
+        WCString::from_vec(self.as_slice())
+    }
+}
+
+impl<'a> std::fmt::Debug for UnicodeString<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        std::fmt::Debug::fmt(&String::from_utf16_lossy(self.as_slice()), f)
+    }
+}
+
+impl<'a> std::convert::TryFrom<&'a WCStr> for UnicodeString<'a> {
+    type Error = TooLongError;
+    fn try_from(s: &'a WCStr) -> Result<UnicodeString<'a>, TooLongError> {
+        UnicodeString::new(s)
+    }
+}
+
+impl<'a> std::convert::TryFrom<&'a WCString> for UnicodeString<'a> {
+    type Error = TooLongError;
+    fn try_from(s: &'a WCString) -> Result<UnicodeString<'a>, TooLongError> {
+        UnicodeString::new(s)
+    }
+}
+
+impl<'a> std::convert::TryFrom<UnicodeString<'a>> for WCString {
+    type Error = NulError;
+    fn try_from(s: UnicodeString<'a>) -> Result<WCString, NulError> {
+        s.to_wcstring()
+    }
+}
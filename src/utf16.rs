@@ -0,0 +1,106 @@
+
+use ::std;
+
+use ::upcase;
+
+/// Check whether a ```u16``` is the first (high) half of a surrogate pair.
+pub fn is_high_surrogate(w: u16) -> bool {
+    (0xD800..0xDC00).contains(&w)
+}
+
+/// Check whether a ```u16``` is the second (low) half of a surrogate pair.
+pub fn is_low_surrogate(w: u16) -> bool {
+    (0xDC00..0xE000).contains(&w)
+}
+
+/// Combine a surrogate pair into a ```char```.
+pub fn decode_pair(high: u16, low: u16) -> char {
+    let c = 0x10000 + (((high as u32) - 0xD800) << 10) + ((low as u32) - 0xDC00);
+    unsafe { std::char::from_u32_unchecked(c) }
+}
+
+/// Encode an iterator of ```char```s as ```u16```s.
+#[derive(Clone, Debug)]
+pub struct EncodeUtf16<I> {
+    chars: I,
+    low: Option<u16>,
+}
+
+pub fn encode<I>(chars: I) -> EncodeUtf16<I>
+    where I: Iterator<Item = char> {
+    EncodeUtf16 {
+        chars: chars,
+        low: None,
+    }
+}
+
+impl<I> Iterator for EncodeUtf16<I>
+    where I: Iterator<Item = char> {
+    type Item = u16;
+    fn next(&mut self) -> Option<u16> {
+        if let Some(l) = self.low.take() {
+            return Some(l);
+        }
+        let c = self.chars.next()?;
+        let mut buf = [0u16; 2];
+        if c.encode_utf16(&mut buf).len() == 2 {
+            self.low = Some(buf[1]);
+        }
+        Some(buf[0])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lo, hi) = self.chars.size_hint();
+        let pending = if self.low.is_some() { 1 } else { 0 };
+        (lo + pending, hi.and_then(|hi| hi.checked_mul(2)).and_then(|hi| hi.checked_add(pending)))
+    }
+}
+
+/// Map a single code unit to uppercase the way the OS does for invariant, ordinal comparisons, using a fixed
+/// table. Surrogates, and characters whose uppercase form is not a single BMP character, are unchanged.
+pub fn to_upper_invariant(w: u16) -> u16 {
+    if w < 0x80 {
+        to_ascii_uppercase(w)
+    }
+    else {
+        upcase::upcase(w)
+    }
+}
+
+/// Map ```a```-```z``` to ```A```-```Z```, leaving every other unit unchanged.
+pub fn to_ascii_uppercase(w: u16) -> u16 {
+    if w >= b'a' as u16 && w <= b'z' as u16 {
+        w - (b'a' - b'A') as u16
+    }
+    else {
+        w
+    }
+}
+
+/// Map ```A```-```Z``` to ```a```-```z```, leaving every other unit unchanged.
+pub fn to_ascii_lowercase(w: u16) -> u16 {
+    if w >= b'A' as u16 && w <= b'Z' as u16 {
+        w + (b'a' - b'A') as u16
+    }
+    else {
+        w
+    }
+}
+
+fn single<I>(mut it: I) -> Option<char>
+    where I: Iterator<Item = char> {
+    match (it.next(), it.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    }
+}
+
+/// Simple (one to one) case folding of a character, so that characters that differ only in case, such as
+/// ```Σ```, ```σ``` and ```ς```, or ```K``` and the Kelvin sign, fold to the same character.
+/// Characters that only have multi-character case mappings, such as ```ß```, are unchanged.
+pub fn simple_fold(c: char) -> char {
+    single(c.to_uppercase())
+        .and_then(|u| single(u.to_lowercase()))
+        .or_else(|| single(c.to_lowercase()))
+        .unwrap_or(c)
+}
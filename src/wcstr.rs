@@ -1,20 +1,88 @@
 
 use ::std;
+use ::std::borrow::Cow;
 use ::std::ffi::{OsString, OsStr};
-use ::std::os::windows::ffi::{OsStringExt, OsStrExt};
-use ::std::path::PathBuf;
+use ::std::path::{Path, PathBuf};
+use ::std::rc::Rc;
+use ::std::sync::Arc;
 
 use ::WCString;
-use ::NoNulError;
+use ::{NulError, NoNulError, Utf16Error, Utf16ErrorKind, ToStringError, BufferTooSmallError};
 use ::error;
+use ::os;
+use ::utf16;
+use ::search;
+use ::cmp;
+use ::chars;
+use ::cmdline;
+use ::{Chars, CharsLossy, CharIndices, CodeUnits, CommandLineArgs};
+use ::display;
+use ::{RedactPolicy, RedactedDisplay, WCDisplay};
+
+/// What ```copy_to_buffer()``` should do when the string does not fit.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TruncatePolicy {
+    /// Fail with ```BufferTooSmallError```, leaving the buffer unchanged.
+    Fail,
+    /// Copy as much as fits without splitting a surrogate pair.
+    Truncate,
+}
 
 /// Representation of a borrowed Win32 style "wide" string.
-#[derive(PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[derive(PartialEq, PartialOrd, Eq, Ord)]
 pub struct WCStr {
     inner: [u16]
 }
 
+/// Return the ```u16```s of ```s``` without the ```nul``` terminator, in a ```const``` context.
+pub const fn units(s: &WCStr) -> &[u16] {
+    s.inner.split_at(s.inner.len() - 1).0
+}
+
+/// Create a ```&WCStr``` in a ```const``` context from a slice that ends with its only ```nul```.
+pub const unsafe fn from_units_with_nul(units: &[u16]) -> &WCStr {
+    std::mem::transmute(units)
+}
+
 impl WCStr {
+    /// Return an empty ```&'static WCStr```, for passing an empty string without allocating.
+    ///
+    /// # ```empty()``` example
+    ///
+    ///     use wcstr::WCStr;
+    ///     let s = WCStr::empty();
+    ///     assert!(s.is_empty());
+    ///     assert!(s.to_slice_with_nul() == [0]);
+    pub const fn empty() -> &'static WCStr {
+        const EMPTY: &[u16] = &[0];
+        unsafe { from_units_with_nul(EMPTY) }
+    }
+
+    /// Create a ```&'static WCStr``` from a ```'static``` slice that ends with its only ```nul```, in a
+    /// ```const``` context, for declaring static tables of "wide" strings without ```unsafe```.
+    ///
+    /// * This will panic if ```units``` is not terminated with its only ```nul```. In a ```const``` or
+    ///   ```static``` initializer, that is a compile time error.
+    ///
+    /// # ```from_static_with_nul()``` example
+    ///
+    ///     use wcstr::WCStr;
+    ///     const KEYS: [&'static WCStr; 2] = [
+    ///         WCStr::from_static_with_nul(&[b'H' as u16, b'K' as u16, b'L' as u16, b'M' as u16, 0]),
+    ///         WCStr::from_static_with_nul(&[b'H' as u16, b'K' as u16, b'C' as u16, b'U' as u16, 0]),
+    ///     ];
+    ///     assert!(*KEYS[1] == *"HKCU");
+    pub const fn from_static_with_nul(units: &'static [u16]) -> &'static WCStr {
+        let len = units.len();
+        assert!(len > 0 && units[len - 1] == 0, "WCStr::from_static_with_nul: missing nul terminator");
+        let mut i = 0;
+        while i < len - 1 {
+            assert!(units[i] != 0, "WCStr::from_static_with_nul: unexpected nul");
+            i += 1;
+        }
+        unsafe { from_units_with_nul(units) }
+    }
+
     /// Create a ```&WCStr``` from a raw pointer and a length.
     ///
     /// This function is unsafe for the reasons mentioned below.
@@ -27,6 +95,7 @@ impl WCStr {
     /// * It points to an array of ```u16```'s that is terminated with a ```nul``` at exactly the offset "```len```".
     ///
     /// This function will assert/panic when ```nul``` is not found at offset "```len```".
+    /// With the ```extra-checks``` feature, debug builds also panic when a ```nul``` is found before offset "```len```".
     ///
     /// The lifetime of the ```&WCStr``` returned from this function is not guranteed to be correct and
     /// it is up to the caller to determine the appropriate lifetime.
@@ -40,8 +109,69 @@ impl WCStr {
     ///     let s = unsafe { WCStr::from_raw_parts(a.as_ptr(), a.len() - 1) };
     ///     assert!(s.len() == (a.len() - 1));
     pub unsafe fn from_raw_parts<'a>(ptr: *const u16, len: usize) -> &'a WCStr {
-        assert!(*ptr.offset(len as isize) == 0u16);
-        std::mem::transmute(std::slice::from_raw_parts(ptr, len + 1))
+        assert!(*ptr.add(len) == 0u16);
+        let slice = std::slice::from_raw_parts(ptr, len + 1);
+        error::check_nul_terminated("WCStr::from_raw_parts", slice);
+        std::mem::transmute(slice)
+    }
+
+    /// Create a ```&WCStr``` from a raw pointer to a ```nul``` terminated string, scanning for the ```nul```.
+    ///
+    /// ```ptr``` must be non-null and point to a ```nul``` terminated string. The lifetime of the ```&WCStr```
+    /// returned from this function is not guranteed to be correct and it is up to the caller to determine
+    /// the appropriate lifetime.
+    ///
+    /// # ```from_ptr()``` example
+    ///
+    ///     use wcstr::{WCStr, WCString};
+    ///     let s = WCString::from_str("testing").unwrap();
+    ///     let t = unsafe { WCStr::from_ptr(s.as_ptr()) };
+    ///     assert!(*t == *s);
+    pub unsafe fn from_ptr<'a>(ptr: *const u16) -> &'a WCStr {
+        let mut len = 0;
+        while *ptr.add(len) != 0 {
+            len += 1;
+        }
+        from_units_with_nul(std::slice::from_raw_parts(ptr, len + 1))
+    }
+
+    /// Create a ```&WCStr``` from a raw pointer that may be null, for APIs that legitimately return ```NULL```.
+    ///
+    /// If ```ptr``` is non-null, it must point to a ```nul``` terminated string (see ```from_ptr()```).
+    ///
+    /// # ```from_ptr_opt()``` example
+    ///
+    ///     use std::ptr;
+    ///     use wcstr::{WCStr, WCString};
+    ///     assert!(unsafe { WCStr::from_ptr_opt(ptr::null()) }.is_none());
+    ///     let s = WCString::from_str("testing").unwrap();
+    ///     assert!(unsafe { WCStr::from_ptr_opt(s.as_ptr()) } == Some(&*s));
+    pub unsafe fn from_ptr_opt<'a>(ptr: *const u16) -> Option<&'a WCStr> {
+        if ptr.is_null() {
+            None
+        }
+        else {
+            Some(WCStr::from_ptr(ptr))
+        }
+    }
+
+    /// Create a ```&WCStr``` from a raw pointer to a buffer of ```max_len``` ```u16```s, such as a fixed-size
+    /// ```WCHAR``` array in a struct, where the ```nul``` terminator is not guaranteed.
+    /// At most ```max_len``` ```u16```s are scanned, and ```NoNulError``` is returned if none of them is ```nul```.
+    ///
+    /// ```ptr``` must be valid for reads of ```max_len``` ```u16```s. The lifetime of the ```&WCStr```
+    /// returned from this function is not guranteed to be correct and it is up to the caller to determine
+    /// the appropriate lifetime.
+    ///
+    /// # ```from_ptr_truncate()``` example
+    ///
+    ///     use wcstr::WCStr;
+    ///     let name = [b'C' as u16, b'O' as u16, b'M' as u16, b'1' as u16, 0, 0, 0, 0];
+    ///     let s = unsafe { WCStr::from_ptr_truncate(name.as_ptr(), name.len()) }.unwrap();
+    ///     assert!(*s == *"COM1");
+    ///     assert!(unsafe { WCStr::from_ptr_truncate(name.as_ptr(), 4) }.is_err());
+    pub unsafe fn from_ptr_truncate<'a>(ptr: *const u16, max_len: usize) -> Result<&'a WCStr, NoNulError> {
+        WCStr::from_slice_with_nul(std::slice::from_raw_parts(ptr, max_len))
     }
 
     /// Create a ```&WCStr``` from a slice of ```u16```'s.
@@ -56,7 +186,47 @@ impl WCStr {
     pub fn from_slice_with_nul<'a>(slice: &'a [u16]) -> Result<&'a WCStr, NoNulError> {
         match slice.iter().position(|x| *x == 0) {
             None => Err(error::no_nul(None)),
-            Some(i) => Ok(unsafe { std::mem::transmute(&slice[..i + 1]) }),
+            Some(i) => Ok(unsafe { std::mem::transmute::<&[u16], &WCStr>(&slice[..i + 1]) }),
+        }
+    }
+
+    /// Create a ```&WCStr``` from a fixed-size ```WCHAR``` array, such as the ```cFileName``` field of
+    /// ```WIN32_FIND_DATAW```, without copying. The string ends at the first ```nul``` in the array, and
+    /// ```NoNulError``` is returned if there is none. For a buffer that is only available as a slice,
+    /// use ```from_slice_with_nul()```.
+    ///
+    /// # ```from_array_with_nul()``` example
+    ///
+    ///     use wcstr::WCStr;
+    ///     let mut file_name = [0u16; 260];
+    ///     for (b, w) in file_name.iter_mut().zip("desktop.ini".encode_utf16()) {
+    ///         *b = w;
+    ///     }
+    ///     let s = WCStr::from_array_with_nul(&file_name).unwrap();
+    ///     assert!(*s == *"desktop.ini");
+    ///     assert!(WCStr::from_array_with_nul(&[b'a' as u16; 4]).is_err());
+    pub fn from_array_with_nul<'a, const N: usize>(array: &'a [u16; N]) -> Result<&'a WCStr, NoNulError> {
+        WCStr::from_slice_with_nul(array)
+    }
+
+    /// Create a ```Cow<WCStr>``` from a slice that may or may not end with a ```nul``` terminator.
+    /// The slice is borrowed if it ends with its only ```nul```, and copied with a ```nul``` appended if it
+    /// contains no ```nul```. ```NulError``` will be returned if it contains any other ```nul```.
+    ///
+    /// # ```from_slice_cow()``` example
+    ///
+    ///     use std::borrow::Cow;
+    ///     use wcstr::WCStr;
+    ///     let with_nul = [b'a' as u16, 0];
+    ///     assert!(match WCStr::from_slice_cow(&with_nul).unwrap() { Cow::Borrowed(_) => true, _ => false });
+    ///     let without = [b'a' as u16];
+    ///     assert!(*WCStr::from_slice_cow(&without).unwrap() == *"a");
+    ///     assert!(WCStr::from_slice_cow(&[0, b'a' as u16]).is_err());
+    pub fn from_slice_cow<'a>(slice: &'a [u16]) -> Result<Cow<'a, WCStr>, NulError> {
+        match slice.iter().position(|&w| w == 0) {
+            None => Ok(Cow::Owned(unsafe { WCString::from_vec_unchecked(slice.to_vec()) })),
+            Some(i) if i == slice.len() - 1 => Ok(Cow::Borrowed(unsafe { from_units_with_nul(slice) })),
+            Some(i) => Err(error::nul(i, None)),
         }
     }
 
@@ -70,6 +240,73 @@ impl WCStr {
         self.inner.len() == 1
     }
 
+    /// Check whether ```index``` lies on a character boundary.
+    ///
+    /// * An index is a boundary unless it points at the low half of a surrogate pair.
+    /// * The start and the end of the string are always boundaries.
+    /// * Any index past the end of the string is not a boundary.
+    ///
+    /// # ```is_char_boundary()``` example
+    ///
+    ///     use wcstr::WCString;
+    ///     let s = WCString::from_str("a\u{1F600}b").unwrap();
+    ///     assert!(s.is_char_boundary(1));
+    ///     assert!(!s.is_char_boundary(2));
+    ///     assert!(s.is_char_boundary(3));
+    ///     assert!(s.is_char_boundary(4));
+    ///     assert!(!s.is_char_boundary(5));
+    pub fn is_char_boundary(&self, index: usize) -> bool {
+        let s = self.to_slice();
+        if index == 0 || index == s.len() {
+            return true;
+        }
+        if index > s.len() {
+            return false;
+        }
+        !(utf16::is_low_surrogate(s[index]) && utf16::is_high_surrogate(s[index - 1]))
+    }
+
+    /// Find the largest character boundary that is not greater than ```index```.
+    ///
+    /// An ```index``` past the end of the string is clamped to the length of the string.
+    ///
+    /// # ```floor_char_boundary()``` example
+    ///
+    ///     use wcstr::WCString;
+    ///     let s = WCString::from_str("a\u{1F600}b").unwrap();
+    ///     assert!(s.floor_char_boundary(2) == 1);
+    ///     assert!(s.floor_char_boundary(3) == 3);
+    ///     assert!(s.floor_char_boundary(100) == 4);
+    pub fn floor_char_boundary(&self, index: usize) -> usize {
+        if index >= self.len() {
+            self.len()
+        }
+        else if self.is_char_boundary(index) {
+            index
+        }
+        else {
+            index - 1
+        }
+    }
+
+    /// Return the first ```u16``` of this "wide" string, or ```None``` if it is empty.
+    pub fn first(&self) -> Option<u16> {
+        self.to_slice().first().cloned()
+    }
+
+    /// Return the last ```u16``` (before the ```nul``` terminator) of this "wide" string, or ```None``` if it is empty.
+    ///
+    /// # ```first()``` / ```last()``` example
+    ///
+    ///     use wcstr::WCString;
+    ///     let s = WCString::from_str("C:\\").unwrap();
+    ///     assert!(s.first() == Some(b'C' as u16));
+    ///     assert!(s.last() == Some(b'\\' as u16));
+    ///     assert!(WCString::new().last() == None);
+    pub fn last(&self) -> Option<u16> {
+        self.to_slice().last().cloned()
+    }
+
     /// Return a raw pointer to this "wide" string.
     ///
     ///  * The pointer remains valid only as long as this string is valid.
@@ -78,6 +315,36 @@ impl WCStr {
         self.inner.as_ptr()
     }
 
+    /// Copy this "wide" string into a fixed-size buffer, always ```nul``` terminating it, and return the number
+    /// of ```u16```s copied, not counting the ```nul```.
+    ///
+    /// If the string does not fit, ```policy``` decides whether to fail or to truncate it. An empty buffer
+    /// always fails, since it can't even hold the ```nul```.
+    ///
+    /// # ```copy_to_buffer()``` example
+    ///
+    ///     use wcstr::{WCString, TruncatePolicy};
+    ///     let s = WCString::from_str("Untitled - Notepad").unwrap();
+    ///     let mut title = [0xFFFFu16; 9];
+    ///     assert!(s.copy_to_buffer(&mut title, TruncatePolicy::Fail).unwrap_err().required() == 19);
+    ///     assert!(title[0] == 0xFFFF);
+    ///     assert!(s.copy_to_buffer(&mut title, TruncatePolicy::Truncate) == Ok(8));
+    ///     assert!(title == *WCString::from_str("Untitled").unwrap());
+    pub fn copy_to_buffer(&self, buffer: &mut [u16], policy: TruncatePolicy) -> Result<usize, BufferTooSmallError> {
+        let len = if self.inner.len() <= buffer.len() {
+            self.len()
+        }
+        else if policy == TruncatePolicy::Truncate && !buffer.is_empty() {
+            self.floor_char_boundary(buffer.len() - 1)
+        }
+        else {
+            return Err(error::buffer_too_small(self.inner.len()));
+        };
+        buffer[..len].copy_from_slice(&self.inner[..len]);
+        buffer[len] = 0;
+        Ok(len)
+    }
+
     /// Return this "wide" string as a slice of ```u16```s without a ```nul``` terminator.
     pub fn to_slice(&self) -> &[u16] {
         &self.inner[..self.len()]
@@ -88,19 +355,105 @@ impl WCStr {
         &self.inner
     }
 
+    /// Return a sub-slice of this "wide" string (without the ```nul``` terminator), or ```None``` if
+    /// ```range``` is out of bounds.
+    ///
+    /// # ```get()``` example
+    ///
+    ///     use wcstr::WCString;
+    ///     let s = WCString::from_str("key=value").unwrap();
+    ///     assert!(s.get(4..) == Some(&s.to_slice()[4..]));
+    ///     assert!(s.get(4..10) == None);
+    pub fn get<R>(&self, range: R) -> Option<&[u16]>
+        where R: std::slice::SliceIndex<[u16], Output = [u16]> {
+        self.to_slice().get(range)
+    }
+
+    /// Return a sub-slice of this "wide" string (without the ```nul``` terminator), without bounds checks.
+    ///
+    /// This function is unsafe as it assumes that ```range``` is within ```0..self.len()```;
+    /// ```get()``` should be used instead unless the offsets have already been validated.
+    ///
+    /// # ```get_unchecked()``` example
+    ///
+    ///     use wcstr::WCString;
+    ///     let s = WCString::from_str("key=value").unwrap();
+    ///     let key = unsafe { s.get_unchecked(..3) };
+    ///     assert!(key.len() == 3);
+    pub unsafe fn get_unchecked<R>(&self, range: R) -> &[u16]
+        where R: std::slice::SliceIndex<[u16], Output = [u16]> {
+        self.inner.get_unchecked(range)
+    }
+
     /// Convert this "wide" string to a ```String``` by using ```String::from_utf16```
     pub fn to_string(&self) -> Result<String, std::string::FromUtf16Error> {
         String::from_utf16(self.to_slice())
     }
 
+    /// Convert this "wide" string to a ```String```, returning the position of the first unpaired
+    /// surrogate and the part of the string before it on failure.
+    ///
+    /// # ```to_string_checked()``` example
+    ///
+    ///     use wcstr::WCString;
+    ///     let s = WCString::from_vec(vec![0x61, 0x62, 0xDC00, 0x63]).unwrap();
+    ///     let e = s.to_string_checked().unwrap_err();
+    ///     assert!(e.offset() == 2 && e.prefix() == "ab");
+    ///     assert!(e.to_string() == "unpaired low surrogate at position: 2 (after \"ab\")");
+    pub fn to_string_checked(&self) -> Result<String, ToStringError> {
+        match self.validate_utf16() {
+            Ok(()) => Ok(String::from_utf16_lossy(self.to_slice())),
+            Err(e) => Err(error::to_string(e, String::from_utf16_lossy(&self.to_slice()[..e.offset()]))),
+        }
+    }
+
     /// Convert this "wide" string to a ```String``` by using ```String::from_utf16_lossy```
     pub fn to_string_lossy(&self) -> String {
         String::from_utf16_lossy(self.to_slice())
     }
 
-    /// Convert this "wide" string to an ```OsString``` by using ```OsString::from_wide```
+    /// Check that this "wide" string is well-formed UTF-16, returning the position and kind of the first
+    /// unpaired surrogate otherwise.
+    ///
+    /// # ```validate_utf16()``` example
+    ///
+    ///     use wcstr::{WCString, Utf16ErrorKind};
+    ///     assert!(WCString::from_str("a\u{1F600}").unwrap().validate_utf16().is_ok());
+    ///     let s = WCString::from_vec(vec![0x61, 0xD83D, 0x62]).unwrap();
+    ///     let e = s.validate_utf16().unwrap_err();
+    ///     assert!(e.offset() == 1 && e.kind() == Utf16ErrorKind::LoneHighSurrogate);
+    pub fn validate_utf16(&self) -> Result<(), Utf16Error> {
+        match self.char_indices().find(|c| c.1.is_err()) {
+            Some((offset, Err(w))) if utf16::is_high_surrogate(w) => {
+                Err(error::utf16(offset, Utf16ErrorKind::LoneHighSurrogate))
+            },
+            Some((offset, _)) => Err(error::utf16(offset, Utf16ErrorKind::LoneLowSurrogate)),
+            None => Ok(()),
+        }
+    }
+
+    /// Convert this "wide" string to UTF-32, combining surrogate pairs.
+    /// Fails on the first unpaired surrogate.
+    ///
+    /// # ```to_utf32()``` example
+    ///
+    ///     use wcstr::WCString;
+    ///     let s = WCString::from_str("a\u{1F600}").unwrap();
+    ///     assert!(s.to_utf32().unwrap() == [0x61, 0x1F600]);
+    pub fn to_utf32(&self) -> Result<Vec<u32>, std::char::DecodeUtf16Error> {
+        std::char::decode_utf16(self.to_slice().iter().cloned()).map(|c| c.map(|c| c as u32)).collect()
+    }
+
+    /// Convert this "wide" string to UTF-32, combining surrogate pairs and replacing unpaired surrogates
+    /// with ```U+FFFD REPLACEMENT CHARACTER```.
+    pub fn to_utf32_lossy(&self) -> Vec<u32> {
+        self.chars_lossy().map(|c| c as u32).collect()
+    }
+
+    /// Convert this "wide" string to an ```OsString``` by using ```OsString::from_wide```.
+    /// On platforms other than Windows, unpaired surrogates are replaced with ```U+FFFD REPLACEMENT CHARACTER```.
     pub fn to_os_string(&self) -> OsString {
-        OsString::from_wide(self.to_slice())
+        os::from_wide(self.to_slice())
     }
 
     /// Convert this "wide" string to a ```PathBuf```
@@ -108,6 +461,364 @@ impl WCStr {
         PathBuf::from(self.to_os_string())
     }
 
+    /// Return an iterator over the characters of this "wide" string.
+    ///
+    /// * Surrogate pairs are combined into a single ```char```.
+    /// * Unpaired surrogates are returned as ```Err(u16)```.
+    /// * The iterator is double-ended, and pairs surrogates correctly when iterating from the back.
+    /// * The iterator is fused, and its size hint is between half and all of the remaining ```u16```s.
+    ///
+    /// # ```chars()``` example
+    ///
+    ///     use wcstr::WCString;
+    ///     let s = WCString::from_str("a\u{1F600}").unwrap();
+    ///     let mut c = s.chars();
+    ///     assert!(c.next_back() == Some(Ok('\u{1F600}')));
+    ///     assert!(c.next_back() == Some(Ok('a')));
+    ///     assert!(c.next_back() == None);
+    ///     assert!(s.chars().size_hint() == (2, Some(3)));
+    pub fn chars<'a>(&'a self) -> Chars<'a> {
+        chars::new(self.to_slice())
+    }
+
+    /// Return an iterator over the ```u16```s of this "wide" string, without the ```nul``` terminator.
+    /// Iterating over a ```&WCStr``` or ```&WCString``` does the same.
+    ///
+    /// # ```code_units()``` example
+    ///
+    ///     use wcstr::WCString;
+    ///     let s = WCString::from_str("ab").unwrap();
+    ///     assert!(s.code_units().rev().collect::<Vec<u16>>() == [0x62, 0x61]);
+    ///     let mut sum = 0;
+    ///     for w in &s {
+    ///         sum += w;
+    ///     }
+    ///     assert!(sum == 0x61 + 0x62);
+    pub fn code_units<'a>(&'a self) -> CodeUnits<'a> {
+        chars::new_code_units(self.to_slice())
+    }
+
+    /// Return an iterator over the characters of this "wide" string and their ```u16``` offsets.
+    ///
+    /// # ```char_indices()``` example
+    ///
+    ///     use wcstr::WCString;
+    ///     let s = WCString::from_str("\u{1F600}b").unwrap();
+    ///     let v: Vec<_> = s.char_indices().collect();
+    ///     assert!(v == [(0, Ok('\u{1F600}')), (2, Ok('b'))]);
+    ///     assert!(s.char_indices().next_back() == Some((2, Ok('b'))));
+    pub fn char_indices<'a>(&'a self) -> CharIndices<'a> {
+        chars::new_indices(self.to_slice())
+    }
+
+    /// Return an iterator over the characters of this "wide" string, replacing unpaired
+    /// surrogates with ```U+FFFD REPLACEMENT CHARACTER```.
+    ///
+    /// # ```chars_lossy()``` example
+    ///
+    ///     use wcstr::WCString;
+    ///     let s = WCString::from_str("path.txt!?").unwrap();
+    ///     let n = s.chars_lossy().rev().take_while(|c| c.is_ascii_punctuation()).count();
+    ///     assert!(n == 2);
+    pub fn chars_lossy<'a>(&'a self) -> CharsLossy<'a> {
+        chars::new_lossy(self.to_slice())
+    }
+
+    /// Split a command line, such as the one returned by ```GetCommandLineW```, into its arguments with the
+    /// same quoting rules as ```CommandLineToArgvW```.
+    ///
+    /// * The first argument is the program name: it ends at the next ```"``` if it starts with one, otherwise
+    ///   at the first space or tab, and backslashes are taken literally.
+    /// * In the other arguments, ```2n``` backslashes followed by ```"``` become ```n``` backslashes and the
+    ///   ```"``` toggles quoting, ```2n+1``` backslashes followed by ```"``` become ```n``` backslashes and a
+    ///   literal ```"```, and ```""``` inside quotes is a literal ```"```.
+    /// * Unlike ```CommandLineToArgvW```, an empty command line yields no arguments instead of the path of
+    ///   the current executable.
+    ///
+    /// # ```command_line_args()``` example
+    ///
+    ///     use wcstr::WCString;
+    ///     let cmd = WCString::from_str(r#""C:\Program Files\app.exe" -o "a b" c\\"d e" x\"y "q""r" \\n"#).unwrap();
+    ///     let args: Vec<String> = cmd.command_line_args().map(|a| a.to_string_lossy()).collect();
+    ///     assert!(args == [r"C:\Program Files\app.exe", "-o", "a b", r"c\d e", r#"x"y"#, r#"q"r"#, r"\\n"]);
+    ///     let cmd = WCString::from_str(" a").unwrap();
+    ///     assert!(cmd.command_line_args().collect::<Vec<_>>() == ["", "a"]);
+    pub fn command_line_args<'a>(&'a self) -> CommandLineArgs<'a> {
+        cmdline::new(self)
+    }
+
+    /// Return an object that implements ```Display``` for this "wide" string, without allocating.
+    ///
+    /// * Unpaired surrogates are written as ```U+FFFD REPLACEMENT CHARACTER```.
+    /// * With the ```log``` feature, the returned reference also implements ```log::kv::ToValue```.
+    ///
+    /// # ```as_display()``` example
+    ///
+    ///     use wcstr::WCString;
+    ///     let s = WCString::from_str("C:\\Windows").unwrap();
+    ///     assert!(format!("opening {}", s.as_display()) == "opening C:\\Windows");
+    pub fn as_display<'a>(&'a self) -> &'a WCDisplay {
+        display::display(self.to_slice())
+    }
+
+    /// Return this "wide" string as a value that can be recorded in ```tracing``` events and spans.
+    ///
+    /// The string is recorded lossily with ```Display``` when the event is actually emitted, so
+    /// nothing is allocated for disabled events. (```tracing::Value``` is sealed, so this is the same
+    /// as ```tracing::field::display(s.as_display())```.)
+    ///
+    /// # ```as_value()``` example
+    ///
+    ///     # extern crate tracing;
+    ///     # extern crate wcstr;
+    ///     # fn main() {
+    ///     use wcstr::WCString;
+    ///     let s = WCString::from_str("C:\\Windows").unwrap();
+    ///     tracing::info!(path = s.as_value(), "opening");
+    ///     # }
+    #[cfg(feature = "tracing")]
+    pub fn as_value<'a>(&'a self) -> ::tracing::field::DisplayValue<&'a WCDisplay> {
+        ::tracing::field::display(self.as_display())
+    }
+
+    /// Return an object that implements ```Display``` for safely logging this "wide" string, masking the
+    /// parts of it selected by ```policy```.
+    ///
+    /// * Unpaired surrogates are written as ```U+FFFD REPLACEMENT CHARACTER```.
+    /// * Nothing is allocated unless the policy is ```RedactPolicy::After```.
+    ///
+    /// # ```display_redacted()``` example
+    ///
+    ///     use wcstr::{WCString, RedactPolicy};
+    ///     let s = WCString::from_str("C:\\Users\\bob\\notes.txt").unwrap();
+    ///     assert!(s.display_redacted(RedactPolicy::AllButLastComponent).to_string() == "***\\notes.txt");
+    ///     let t = WCString::from_str("user=bob;password=hunter2").unwrap();
+    ///     assert!(t.display_redacted(RedactPolicy::After("password=")).to_string() == "user=bob;password=***");
+    pub fn display_redacted<'a>(&'a self, policy: RedactPolicy<'a>) -> RedactedDisplay<'a> {
+        display::redacted(self.to_slice(), policy)
+    }
+
+    /// Compute a stable 64-bit hash of this "wide" string.
+    ///
+    /// Unlike the ```Hash``` implementation, the result does not depend on the hasher, the platform
+    /// or the version of Rust, so it can be exchanged between processes and persisted.
+    ///
+    /// The hash is 64-bit FNV-1a over the code units (without the ```nul``` terminator), each fed in
+    /// little-endian byte order. This is the same as FNV-1a over the UTF-16LE encoding of the string.
+    /// It is not a cryptographic hash.
+    ///
+    /// # ```stable_hash()``` example
+    ///
+    ///     use wcstr::WCString;
+    ///     let s = WCString::from_str("abc").unwrap();
+    ///     assert!(s.stable_hash() == 0xcec64e155111225d);
+    ///     assert!(WCString::new().stable_hash() == 0xcbf29ce484222325);
+    pub fn stable_hash(&self) -> u64 {
+        const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+        const PRIME: u64 = 0x100000001b3;
+        let mut hash = OFFSET_BASIS;
+        for &w in self.to_slice() {
+            hash = (hash ^ (w & 0xFF) as u64).wrapping_mul(PRIME);
+            hash = (hash ^ (w >> 8) as u64).wrapping_mul(PRIME);
+        }
+        hash
+    }
+
+    /// Return a multi-line diagnostic view of this "wide" string, for debugging encoding issues.
+    ///
+    /// There is one row per character: the ```u16``` offset, the code unit(s) in hex, and the decoded
+    /// character (or ```<lone surrogate>```).
+    ///
+    /// # ```dump()``` example
+    ///
+    ///     use wcstr::WCString;
+    ///     let mut s = WCString::from_str("a\u{1F600}").unwrap();
+    ///     s.push_slice(&[0xD800u16]).unwrap();
+    ///     assert!(s.dump() == "     0  0061       'a'\n     1  D83D DE00  '\u{1F600}'\n     3  D800       <lone surrogate>\n");
+    pub fn dump(&self) -> String {
+        let mut s = String::new();
+        self.write_dump(&mut s).unwrap();
+        s
+    }
+
+    /// Write the diagnostic view returned by ```dump()``` into ```w```.
+    pub fn write_dump<W>(&self, w: &mut W) -> std::fmt::Result
+        where W: std::fmt::Write {
+        let units = self.to_slice();
+        let mut index = 0;
+        for c in self.chars() {
+            match c {
+                Ok(c) if c.len_utf16() == 2 => {
+                    writeln!(w, "{:>6}  {:04X} {:04X}  {:?}", index, units[index], units[index + 1], c)?;
+                    index += 2;
+                },
+                Ok(c) => {
+                    writeln!(w, "{:>6}  {:04X}       {:?}", index, units[index], c)?;
+                    index += 1;
+                },
+                Err(u) => {
+                    writeln!(w, "{:>6}  {:04X}       <lone surrogate>", index, u)?;
+                    index += 1;
+                },
+            }
+        }
+        Ok(())
+    }
+
+    fn map_chars<F, I>(&self, f: F) -> WCString
+        where F: Fn(char) -> I, I: Iterator<Item = char> {
+        let mut v = Vec::with_capacity(self.len() + 1);
+        let mut buf = [0u16; 2];
+        for c in self.chars() {
+            match c {
+                Ok(c) => {
+                    for c in f(c) {
+                        v.extend_from_slice(c.encode_utf16(&mut buf));
+                    }
+                },
+                Err(w) => v.push(w),
+            }
+        }
+        unsafe { WCString::from_vec_unchecked(v) }
+    }
+
+    /// Convert this "wide" string to uppercase with full Unicode case mapping, like ```str::to_uppercase()```.
+    /// The result can be longer than the original (```ß``` becomes ```SS```). Unpaired surrogates are kept.
+    ///
+    /// # ```to_uppercase()``` example
+    ///
+    ///     use wcstr::WCString;
+    ///     let s = WCString::from_str("straße \u{10428}").unwrap();
+    ///     assert!(s.to_uppercase() == "STRASSE \u{10400}");
+    pub fn to_uppercase(&self) -> WCString {
+        self.map_chars(char::to_uppercase)
+    }
+
+    /// Convert this "wide" string to lowercase with full Unicode case mapping. Unpaired surrogates are kept.
+    ///
+    /// * Unlike ```str::to_lowercase()```, every ```Σ``` becomes ```σ```, including at the end of a word.
+    ///
+    /// # ```to_lowercase()``` example
+    ///
+    ///     use wcstr::WCString;
+    ///     let mut v: Vec<u16> = "İSTANBUL ".encode_utf16().collect();
+    ///     v.push(0xD800);
+    ///     let s = WCString::from_vec(v).unwrap();
+    ///     let mut expected: Vec<u16> = "i\u{307}stanbul ".encode_utf16().collect();
+    ///     expected.push(0xD800);
+    ///     assert!(s.to_lowercase() == &expected[..]);
+    pub fn to_lowercase(&self) -> WCString {
+        self.map_chars(char::to_lowercase)
+    }
+
+    /// Convert this "wide" string to uppercase using the invariant, per code unit mapping that Windows
+    /// uses for ordinal case-insensitive comparisons (e.g. of kernel object, registry and file names).
+    ///
+    /// This is not full Unicode case mapping: each ```u16``` is mapped on its own to its simple uppercase
+    /// form from a fixed table, and is left unchanged if that form is not a single BMP character (so ```ß```
+    /// stays ```ß```). Surrogates are never changed, and neither are ```ı``` and ```ſ```, whose Unicode uppercase forms are the
+    /// ASCII ```I``` and ```S```.
+    ///
+    /// # ```to_upper_invariant()``` example
+    ///
+    ///     use wcstr::WCString;
+    ///     let s = WCString::from_str("Global\\straße").unwrap();
+    ///     assert!(s.to_upper_invariant().to_string().unwrap() == "GLOBAL\\STRAßE");
+    ///     let s = WCString::from_str("\u{131}\u{17F}").unwrap();
+    ///     assert!(s.to_upper_invariant() == s);
+    pub fn to_upper_invariant(&self) -> WCString {
+        let v: Vec<u16> = self.to_slice().iter().map(|&w| utf16::to_upper_invariant(w)).collect();
+        unsafe { WCString::from_vec_unchecked(v) }
+    }
+
+    /// Compare two "wide" strings after mapping them with ```to_upper_invariant()```, without allocating.
+    ///
+    /// Two names that compare ```Equal``` here collide in the OS's case-insensitive namespaces.
+    ///
+    /// # ```cmp_upper_invariant()``` example
+    ///
+    ///     use std::cmp::Ordering;
+    ///     use wcstr::WCString;
+    ///     let s = WCString::from_str("HKEY_Local_Machine").unwrap();
+    ///     let t = WCString::from_str("hkey_local_machine").unwrap();
+    ///     assert!(s.cmp_upper_invariant(&t) == Ordering::Equal);
+    ///     let dotless = WCString::from_str("\u{131}").unwrap();
+    ///     let long_s = WCString::from_str("\u{17F}").unwrap();
+    ///     assert!(dotless.cmp_upper_invariant(WCString::from_str("i").unwrap()) != Ordering::Equal);
+    ///     assert!(long_s.cmp_upper_invariant(WCString::from_str("s").unwrap()) != Ordering::Equal);
+    pub fn cmp_upper_invariant<T>(&self, other: T) -> std::cmp::Ordering
+        where T: AsRef<WCStr> {
+        let a = self.to_slice().iter().map(|&w| utf16::to_upper_invariant(w));
+        let b = other.as_ref().to_slice().iter().map(|&w| utf16::to_upper_invariant(w));
+        a.cmp(b)
+    }
+
+    /// Compare two "wide" strings the way ```StrCmpLogicalW``` does, so file names sort like they do in
+    /// Explorer: runs of ASCII digits are compared by their numeric value, and everything else is compared
+    /// case-insensitively. When two strings only differ in leading zeros, the one with more zeros comes first.
+    ///
+    /// * Unlike ```StrCmpLogicalW```, non-digits are compared like ```cmp_upper_invariant()``` instead of with
+    ///   the user's locale, so punctuation can sort differently.
+    ///
+    /// # ```cmp_logical()``` example
+    ///
+    ///     use wcstr::WCString;
+    ///     let mut names: Vec<WCString> = ["file10.txt", "File2.txt", "file1.txt", "file01.txt"].iter()
+    ///         .map(|s| WCString::from_str(s).unwrap())
+    ///         .collect();
+    ///     names.sort_by(|a, b| a.cmp_logical(b));
+    ///     assert!(names == ["file01.txt", "file1.txt", "File2.txt", "file10.txt"]);
+    pub fn cmp_logical<T>(&self, other: T) -> std::cmp::Ordering
+        where T: AsRef<WCStr> {
+        cmp::natural(self.to_slice(), other.as_ref().to_slice())
+    }
+
+    /// Check whether two strings are equal, ignoring the case of ASCII letters only.
+    ///
+    /// # ```eq_ignore_ascii_case()``` example
+    ///
+    ///     use wcstr::{WCStr, WCString};
+    ///     let s = WCString::from_str("Path").unwrap();
+    ///     assert!(s.eq_ignore_ascii_case(WCString::from_str("PATH").unwrap()));
+    ///     assert!(!s.eq_ignore_ascii_case(WCString::from_str("PATHEXT").unwrap()));
+    pub fn eq_ignore_ascii_case<T>(&self, other: T) -> bool
+        where T: AsRef<WCStr> {
+        let (a, b) = (self.to_slice(), other.as_ref().to_slice());
+        a.len() == b.len()
+            && a.iter().zip(b.iter()).all(|(&a, &b)| utf16::to_ascii_lowercase(a) == utf16::to_ascii_lowercase(b))
+    }
+
+    /// Check whether this string is equal to an ```&OsStr```, ignoring the case of ASCII letters only.
+    ///
+    /// # ```eq_ignore_ascii_case_str()``` example
+    ///
+    ///     use wcstr::{WCStr, WCString};
+    ///     let s = WCString::from_str("HTTPS").unwrap();
+    ///     assert!(s.eq_ignore_ascii_case_str("https"));
+    ///     assert!(!s.eq_ignore_ascii_case_str("http"));
+    pub fn eq_ignore_ascii_case_str<T>(&self, other: T) -> bool
+        where T: AsRef<OsStr> {
+        let a = self.to_slice().iter().map(|&w| utf16::to_ascii_lowercase(w));
+        let b = os::encode_wide(other.as_ref()).map(utf16::to_ascii_lowercase);
+        a.eq(b)
+    }
+
+    /// Check whether two strings are equal, ignoring case using Unicode simple case folding.
+    /// Surrogate pairs are decoded before folding, and unpaired surrogates must match exactly.
+    ///
+    /// # ```eq_ignore_case()``` example
+    ///
+    ///     use wcstr::{WCStr, WCString};
+    ///     let s = WCString::from_str("ΣΊΣΥΦΟΣ.txt").unwrap();
+    ///     assert!(s.eq_ignore_case(WCString::from_str("σίσυφος.TXT").unwrap()));
+    ///     let s = WCString::from_str("\u{10400}").unwrap();
+    ///     assert!(s.eq_ignore_case(WCString::from_str("\u{10428}").unwrap()));
+    pub fn eq_ignore_case<T>(&self, other: T) -> bool
+        where T: AsRef<WCStr> {
+        let fold = |c: Result<char, u16>| c.map(utf16::simple_fold);
+        self.chars().map(&fold).eq(other.as_ref().chars().map(&fold))
+    }
+
     /// starts with a string.
     ///
     /// # ```starts_with()``` example
@@ -143,10 +854,10 @@ impl WCStr {
     pub fn starts_with_str<T>(&self, s: T) -> bool
         where T: AsRef<OsStr> {
         let s = s.as_ref();
-        let mut s_iter = s.encode_wide();
+        let s_iter = os::encode_wide(s);
         let mut t_iter = self.to_slice().iter();
 
-        while let Some(a) = s_iter.next() {
+        for a in s_iter {
             match t_iter.next() {
                 Some(&b) if a == b => (),
                 _ => return false,
@@ -155,32 +866,462 @@ impl WCStr {
 
         true
     }
+
+    /// ends with a string.
+    ///
+    /// # ```ends_with()``` example
+    ///
+    ///     use wcstr::{WCStr, WCString};
+    ///     let s = WCString::from_str("abcefg").unwrap();
+    ///     let t = WCString::from_str("efg").unwrap();
+    ///     let u = WCString::from_str("abc").unwrap();
+    ///     let v = WCString::from_str("_abcefg").unwrap();
+    ///     assert!(s.ends_with(t));
+    ///     assert!(!s.ends_with(u));
+    ///     assert!(!s.ends_with(v));
+    pub fn ends_with<T>(&self, s: T) -> bool
+        where T: AsRef<WCStr> {
+        self.to_slice().ends_with(s.as_ref().to_slice())
+    }
+
+    /// ends with a string.
+    ///
+    /// # ```ends_with_str()``` example
+    ///
+    ///     use wcstr::{WCStr, WCString};
+    ///     let s = WCString::from_str("setup.exe").unwrap();
+    ///     assert!(s.ends_with_str(".exe"));
+    ///     assert!(!s.ends_with_str(".dll"));
+    ///     assert!(!s.ends_with_str("my_setup.exe"));
+    ///     assert!(s.ends_with_str("") && s.ends_with_str("setup.exe"));
+    pub fn ends_with_str<T>(&self, s: T) -> bool
+        where T: AsRef<OsStr> {
+        let s = s.as_ref();
+        let t = self.to_slice();
+        let len = os::encode_wide(s).count();
+        len <= t.len() && t[t.len() - len..].iter().cloned().eq(os::encode_wide(s))
+    }
+
+    /// contains a string.
+    ///
+    /// # ```contains()``` example
+    ///
+    ///     use wcstr::{WCStr, WCString};
+    ///     let s = WCString::from_str("app.exe --verbose").unwrap();
+    ///     assert!(s.contains(WCString::from_str("--verbose").unwrap()));
+    ///     assert!(!s.contains(WCString::from_str("--quiet").unwrap()));
+    pub fn contains<T>(&self, s: T) -> bool
+        where T: AsRef<WCStr> {
+        search::find(self.to_slice(), s.as_ref().to_slice()).is_some()
+    }
+
+    /// contains a string.
+    ///
+    /// # ```contains_str()``` example
+    ///
+    ///     use wcstr::{WCStr, WCString};
+    ///     let s = WCString::from_str("app.exe --verbose").unwrap();
+    ///     assert!(s.contains_str("--verbose"));
+    ///     assert!(!s.contains_str("--quiet"));
+    ///     assert!(s.contains_str("") && s.contains_str("app.exe --verbose"));
+    ///     assert!(!s.contains_str("app.exe --verbose "));
+    pub fn contains_str<T>(&self, s: T) -> bool
+        where T: AsRef<OsStr> {
+        search::find_units(self.to_slice(), os::encode_wide(s.as_ref())).is_some()
+    }
+
+    /// Return the offset in ```u16``` units of the first occurrence of ```s```.
+    ///
+    /// # ```find()``` example
+    ///
+    ///     use wcstr::{WCStr, WCString};
+    ///     let s = WCString::from_str("C:\\Program Files\\App\\app.exe").unwrap();
+    ///     let sep = [b'\\' as u16];
+    ///     assert!(s.find(&sep) == Some(2));
+    ///     assert!(s.find(WCString::from_str("App").unwrap()) == Some(17));
+    ///     assert!(s.find(WCString::from_str("Windows").unwrap()) == None);
+    pub fn find<T>(&self, s: T) -> Option<usize>
+        where T: AsRef<[u16]> {
+        search::find(self.to_slice(), s.as_ref())
+    }
+
+    /// Return the offset in ```u16``` units of the last occurrence of ```s```.
+    ///
+    /// # ```rfind()``` example
+    ///
+    ///     use wcstr::{WCStr, WCString};
+    ///     let s = WCString::from_str("C:\\Program Files\\App\\app.exe").unwrap();
+    ///     let sep = [b'\\' as u16];
+    ///     let i = s.rfind(&sep).unwrap();
+    ///     assert!(i == 20);
+    ///     assert!(WCString::from_str("app.exe").unwrap() == s.to_slice()[i + 1..]);
+    pub fn rfind<T>(&self, s: T) -> Option<usize>
+        where T: AsRef<[u16]> {
+        search::rfind(self.to_slice(), s.as_ref())
+    }
+
+    /// Return the string with every ```needle``` replaced with ```replacement```, only allocating if
+    /// ```needle``` is present.
+    ///
+    /// * This will assert if either ```needle``` or ```replacement``` is ```nul```
+    ///
+    /// # ```replaced()``` example
+    ///
+    ///     use std::borrow::Cow;
+    ///     use wcstr::{WCStr, WCString};
+    ///     let s = WCString::from_str("C:/Windows/System32").unwrap();
+    ///     assert!(*s.replaced(b'/' as u16, b'\\' as u16) == *"C:\\Windows\\System32");
+    ///     let s = WCString::from_str("C:\\Windows").unwrap();
+    ///     assert!(match s.replaced(b'/' as u16, b'\\' as u16) { Cow::Borrowed(_) => true, _ => false });
+    pub fn replaced<'a>(&'a self, needle: u16, replacement: u16) -> Cow<'a, WCStr> {
+        assert!(needle != 0);
+        assert!(replacement != 0);
+        if self.to_slice().contains(&needle) {
+            let mut s = self.to_owned();
+            s.replace(needle, replacement);
+            Cow::Owned(s)
+        }
+        else {
+            Cow::Borrowed(self)
+        }
+    }
+
+    /// Return the rest of the string after ```prefix```, if it starts with ```prefix```.
+    /// The rest is still a ```nul``` terminated ```&WCStr```.
+    ///
+    /// # ```strip_prefix()``` example
+    ///
+    ///     use wcstr::{WCStr, WCString};
+    ///     let s = WCString::from_str("\\\\?\\C:\\Windows").unwrap();
+    ///     let verbatim = WCString::from_str("\\\\?\\").unwrap();
+    ///     assert!(s.strip_prefix(&verbatim).unwrap() == "C:\\Windows");
+    ///     assert!(s.strip_prefix(WCString::from_str("C:").unwrap()) == None);
+    pub fn strip_prefix<T>(&self, prefix: T) -> Option<&WCStr>
+        where T: AsRef<[u16]> {
+        let prefix = prefix.as_ref();
+        if self.to_slice().starts_with(prefix) {
+            Some(unsafe { from_units_with_nul(&self.inner[prefix.len()..]) })
+        }
+        else {
+            None
+        }
+    }
+
+    /// Return the string before ```suffix``` without a ```nul``` terminator, if it ends with ```suffix```.
+    ///
+    /// # ```strip_suffix()``` example
+    ///
+    ///     use wcstr::{WCStr, WCString};
+    ///     let s = WCString::from_str("C:\\Windows\\").unwrap();
+    ///     let rest = s.strip_suffix(&[b'\\' as u16]).unwrap();
+    ///     assert!(WCString::from_str("C:\\Windows").unwrap() == *rest);
+    ///     assert!(s.strip_suffix(&[b'/' as u16]) == None);
+    pub fn strip_suffix<T>(&self, suffix: T) -> Option<&[u16]>
+        where T: AsRef<[u16]> {
+        let suffix = suffix.as_ref();
+        let s = self.to_slice();
+        if s.ends_with(suffix) {
+            Some(&s[..s.len() - suffix.len()])
+        }
+        else {
+            None
+        }
+    }
+}
+
+macro_rules! impl_eq_os_str {
+    ($($t:ty),*) => {
+        $(
+            impl<'a> PartialEq<$t> for WCStr {
+                fn eq(&self, other: &$t) -> bool {
+                    let other: &OsStr = AsRef::<OsStr>::as_ref(other);
+                    self.to_slice().iter().cloned().eq(os::encode_wide(other))
+                }
+            }
+        )*
+    }
+}
+
+impl_eq_os_str!(str, &'a str, String, OsStr, &'a OsStr, OsString, Path, &'a Path, PathBuf);
+
+macro_rules! impl_eq_reversed {
+    ($($t:ty),*) => {
+        $(
+            impl<'a> PartialEq<WCStr> for $t {
+                fn eq(&self, other: &WCStr) -> bool {
+                    PartialEq::eq(other, self)
+                }
+            }
+        )*
+    }
+}
+
+impl_eq_reversed!(str, &'a str, String, OsStr, &'a OsStr, OsString);
+
+macro_rules! impl_ord_os_str {
+    ($($t:ty),*) => {
+        $(
+            impl<'a> PartialOrd<$t> for WCStr {
+                fn partial_cmp(&self, other: &$t) -> Option<std::cmp::Ordering> {
+                    let other: &OsStr = AsRef::<OsStr>::as_ref(other);
+                    Some(self.to_slice().iter().cloned().cmp(os::encode_wide(other)))
+                }
+            }
+
+            impl<'a> PartialOrd<WCStr> for $t {
+                fn partial_cmp(&self, other: &WCStr) -> Option<std::cmp::Ordering> {
+                    PartialOrd::partial_cmp(other, self).map(std::cmp::Ordering::reverse)
+                }
+            }
+        )*
+    }
+}
+
+/// Compare a "wide" string with an ```OsStr``` by streaming ```encode_wide```, without allocating.
+///
+/// Strings are ordered by ```u16``` units, like ```WCStr``` itself, which can differ from the ordering
+/// of ```OsStr``` for characters above ```U+FFFF```.
+///
+/// # ```OsStr``` comparison example
+///
+///     use std::ffi::{OsStr, OsString};
+///     use wcstr::WCString;
+///     let s = WCString::from_str("PATH").unwrap();
+///     assert!(OsStr::new("PATH") == *s);
+///     assert!(OsString::from("PATH") == s);
+///     assert!(*s < *OsStr::new("PATHEXT"));
+///     assert!(OsString::from("TEMP") > s);
+impl PartialOrd<OsStr> for WCStr {
+    fn partial_cmp(&self, other: &OsStr) -> Option<std::cmp::Ordering> {
+        Some(self.to_slice().iter().cloned().cmp(os::encode_wide(other)))
+    }
+}
+
+impl PartialOrd<WCStr> for OsStr {
+    fn partial_cmp(&self, other: &WCStr) -> Option<std::cmp::Ordering> {
+        PartialOrd::partial_cmp(other, self).map(std::cmp::Ordering::reverse)
+    }
+}
+
+impl_ord_os_str!(&'a OsStr, OsString);
+
+/// Compare a "wide" string with a ```u16``` slice that does not contain the ```nul``` terminator.
+///
+/// Comparisons with ```[u16]``` are consistent with the ```Eq```, ```Ord``` and ```Hash``` implementations
+/// of ```WCStr``` and ```WCString```, which is what makes ```Borrow<[u16]>``` possible.
+///
+/// # ```[u16]``` comparison example
+///
+///     use std::collections::BTreeSet;
+///     use wcstr::WCString;
+///     let s = WCString::from_str("abc").unwrap();
+///     let legacy: Vec<u16> = "abd".encode_utf16().collect();
+///     assert!(*s < legacy[..]);
+///     assert!(legacy[..] > *s);
+///     let mut set = BTreeSet::new();
+///     set.insert(s.clone());
+///     assert!(set.contains(s.to_slice()));
+///     let mut sorted: Vec<Vec<u16>> = vec![legacy, "abb".encode_utf16().collect()];
+///     sorted.sort();
+///     assert!(sorted.binary_search_by(|v| v[..].partial_cmp(&*s).unwrap()).is_err());
+impl PartialEq<[u16]> for WCStr {
+    fn eq(&self, other: &[u16]) -> bool {
+        self.to_slice() == other
+    }
+}
+
+impl PartialEq<WCStr> for [u16] {
+    fn eq(&self, other: &WCStr) -> bool {
+        self == other.to_slice()
+    }
+}
+
+impl PartialOrd<[u16]> for WCStr {
+    fn partial_cmp(&self, other: &[u16]) -> Option<std::cmp::Ordering> {
+        self.to_slice().partial_cmp(other)
+    }
+}
+
+impl PartialOrd<WCStr> for [u16] {
+    fn partial_cmp(&self, other: &WCStr) -> Option<std::cmp::Ordering> {
+        self.partial_cmp(other.to_slice())
+    }
 }
 
-impl<T: ?Sized + AsRef<OsStr>> PartialEq<T> for WCStr {
-    fn eq(&self, other: &T) -> bool {
-        self.to_slice().iter().zip(other.as_ref().encode_wide()).all(|(&a, b)| a == b)
+impl<'a> PartialEq<&'a [u16]> for WCStr {
+    fn eq(&self, other: &&'a [u16]) -> bool {
+        self.to_slice() == *other
+    }
+}
+
+impl<'a> PartialEq<WCStr> for &'a [u16] {
+    fn eq(&self, other: &WCStr) -> bool {
+        *self == other.to_slice()
+    }
+}
+
+impl<'a> PartialOrd<&'a [u16]> for WCStr {
+    fn partial_cmp(&self, other: &&'a [u16]) -> Option<std::cmp::Ordering> {
+        self.to_slice().partial_cmp(*other)
+    }
+}
+
+impl<'a> PartialOrd<WCStr> for &'a [u16] {
+    fn partial_cmp(&self, other: &WCStr) -> Option<std::cmp::Ordering> {
+        (*self).partial_cmp(other.to_slice())
+    }
+}
+
+/// Return ```buffer``` up to its first ```nul```, or all of it if there is none.
+pub fn until_nul(buffer: &[u16]) -> &[u16] {
+    match buffer.iter().position(|&w| w == 0) {
+        Some(i) => &buffer[..i],
+        None => buffer,
+    }
+}
+
+/// Compare a "wide" string with a fixed-size ```WCHAR``` buffer, such as the ```cFileName``` field of
+/// ```WIN32_FIND_DATAW```. The buffer is read up to its first ```nul```, or to its end if there is none.
+///
+/// # ```[u16; N]``` comparison example
+///
+///     use wcstr::WCString;
+///     let mut buffer = [0u16; 260];
+///     for (b, w) in buffer.iter_mut().zip("notepad.exe".encode_utf16()) {
+///         *b = w;
+///     }
+///     let s = WCString::from_str("notepad.exe").unwrap();
+///     assert!(s == buffer);
+///     assert!(buffer == *s);
+///     assert!(s > [b'a' as u16, 0]);
+impl<const N: usize> PartialEq<[u16; N]> for WCStr {
+    fn eq(&self, other: &[u16; N]) -> bool {
+        self.to_slice() == until_nul(other)
+    }
+}
+
+impl<const N: usize> PartialEq<WCStr> for [u16; N] {
+    fn eq(&self, other: &WCStr) -> bool {
+        until_nul(self) == other.to_slice()
+    }
+}
+
+impl<const N: usize> PartialOrd<[u16; N]> for WCStr {
+    fn partial_cmp(&self, other: &[u16; N]) -> Option<std::cmp::Ordering> {
+        self.to_slice().partial_cmp(until_nul(other))
+    }
+}
+
+impl<const N: usize> PartialOrd<WCStr> for [u16; N] {
+    fn partial_cmp(&self, other: &WCStr) -> Option<std::cmp::Ordering> {
+        until_nul(self).partial_cmp(other.to_slice())
+    }
+}
+
+impl std::hash::Hash for WCStr {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.to_slice().hash(state)
+    }
+}
+
+/// Index the ```u16```s of a "wide" string. The ```nul``` terminator cannot be indexed.
+///
+/// # ```Index``` example
+///
+///     use wcstr::WCString;
+///     let s = WCString::from_str("C:\\Windows").unwrap();
+///     assert!(s.len() > 2 && s[1] == b':' as u16);
+impl std::ops::Index<usize> for WCStr {
+    type Output = u16;
+    fn index(&self, index: usize) -> &u16 {
+        &self.to_slice()[index]
+    }
+}
+
+/// Pass an optional "wide" string to Win32 functions that accept ```NULL``` for optional string arguments.
+pub trait AsOptPtr {
+    /// Return a pointer to the string, or ```ptr::null()``` if there is none.
+    ///
+    /// # ```as_opt_ptr()``` example
+    ///
+    ///     use wcstr::{AsOptPtr, WCStr, WCString};
+    ///     let s = WCString::from_str("MyWindowClass").unwrap();
+    ///     assert!(Some(&s).as_opt_ptr() == s.as_ptr());
+    ///     assert!(None::<&WCStr>.as_opt_ptr().is_null());
+    fn as_opt_ptr(&self) -> *const u16;
+}
+
+impl<T: AsRef<WCStr>> AsOptPtr for Option<T> {
+    fn as_opt_ptr(&self) -> *const u16 {
+        match *self {
+            Some(ref s) => s.as_ref().as_ptr(),
+            None => std::ptr::null(),
+        }
     }
 }
 
 impl std::fmt::Debug for WCStr {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        try!(write!(f, "\""));
+        write!(f, "\"")?;
         for &w in self.to_slice().iter() {
-            if w < 0xD800 || w >= 0xE000 {
+            if !(0xD800..0xE000).contains(&w) {
                 for c in std::char::from_u32(w as u32).unwrap().escape_default() {
                     use std::fmt::Write;
-                    try!(f.write_char(c));
+                    f.write_char(c)?;
                 }
             }
             else {
-                try!(write!(f, "\\u{{{:X}}}", w));
+                write!(f, "\\u{{{:X}}}", w)?;
             }
         }
         write!(f, "\"")
     }
 }
 
+/// Writes the string lossily, replacing unpaired surrogates with ```U+FFFD REPLACEMENT CHARACTER```,
+/// without allocating. Width, fill, alignment and precision work as for ```str```.
+///
+/// # ```Display``` example
+///
+///     use wcstr::WCString;
+///     let s = WCString::from_str("C:\\Users").unwrap();
+///     assert!(format!("<{}>", s) == "<C:\\Users>");
+///     let s = WCString::from_vec(vec![0x61, 0xD800]).unwrap();
+///     assert!(format!("{}", s) == "a\u{FFFD}");
+///     let s = WCString::from_str("héllo").unwrap();
+///     assert!(format!("{:>8}", s) == "   héllo");
+///     assert!(format!("{:.2}", s) == "hé");
+///     assert!(format!("{:*^9.4}", s) == "**héll***");
+///     assert!(format!("{:8}|", s) == "héllo   |");
+impl std::fmt::Display for WCStr {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        display::write_lossy(f, self.to_slice())
+    }
+}
+
+/// Same as ```from_slice_with_nul()```.
+///
+/// # ```TryFrom<&[u16]>``` example
+///
+///     use std::convert::TryFrom;
+///     use wcstr::WCStr;
+///     let buffer = [b'a' as u16, b'b' as u16, 0, 0];
+///     let s = <&WCStr>::try_from(&buffer[..]).unwrap();
+///     assert!(s == "ab");
+///     assert!(<&WCStr>::try_from(&buffer[..2]).is_err());
+impl<'a> std::convert::TryFrom<&'a [u16]> for &'a WCStr {
+    type Error = NoNulError;
+
+    fn try_from(slice: &'a [u16]) -> Result<&'a WCStr, NoNulError> {
+        WCStr::from_slice_with_nul(slice)
+    }
+}
+
+impl<'a> Default for &'a WCStr {
+    fn default() -> &'a WCStr {
+        WCStr::empty()
+    }
+}
 
 impl AsRef<WCStr> for WCStr {
     fn as_ref(&self) -> &WCStr {
@@ -194,6 +1335,12 @@ impl AsRef<[u16]> for WCStr {
     }
 }
 
+impl std::borrow::Borrow<[u16]> for WCStr {
+    fn borrow(&self) -> &[u16] {
+        self.to_slice()
+    }
+}
+
 impl ToOwned for WCStr {
     type Owned = WCString;
     fn to_owned(&self) -> WCString {
@@ -203,3 +1350,67 @@ impl ToOwned for WCStr {
     }
 }
 
+/// # ```Box<WCStr>``` example
+///
+///     use wcstr::{WCStr, WCString};
+///     let s = WCString::from_str("MyWindowClass").unwrap();
+///     let b: Box<WCStr> = Box::from(&*s);
+///     assert!(*b == *s);
+///     let b = s.into_boxed_wcstr();
+///     let s = WCString::from(b.clone());
+///     assert!(*b == *s && s.as_slice_with_nul().len() == 14);
+impl<'a> From<&'a WCStr> for Box<WCStr> {
+    fn from(s: &'a WCStr) -> Box<WCStr> {
+        let b: Box<[u16]> = Box::from(&s.inner);
+        unsafe { Box::from_raw(Box::into_raw(b) as *mut WCStr) }
+    }
+}
+
+impl Clone for Box<WCStr> {
+    fn clone(&self) -> Box<WCStr> {
+        Box::from(&**self)
+    }
+}
+
+impl Default for Box<WCStr> {
+    fn default() -> Box<WCStr> {
+        Box::from(WCStr::empty())
+    }
+}
+
+/// # ```Arc<WCStr>``` example
+///
+///     use std::sync::Arc;
+///     use std::thread;
+///     use wcstr::{WCStr, WCString};
+///     let key: Arc<WCStr> = Arc::from(WCString::from_str("Software\\Vendor").unwrap());
+///     let shared = key.clone();
+///     thread::spawn(move || assert!(*shared == *"Software\\Vendor")).join().unwrap();
+///     assert!(key.len() == 15);
+impl<'a> From<&'a WCStr> for Arc<WCStr> {
+    fn from(s: &'a WCStr) -> Arc<WCStr> {
+        let arc: Arc<[u16]> = Arc::from(&s.inner);
+        unsafe { Arc::from_raw(Arc::into_raw(arc) as *const WCStr) }
+    }
+}
+
+impl<'a> From<&'a WCStr> for Rc<WCStr> {
+    fn from(s: &'a WCStr) -> Rc<WCStr> {
+        let rc: Rc<[u16]> = Rc::from(&s.inner);
+        unsafe { Rc::from_raw(Rc::into_raw(rc) as *const WCStr) }
+    }
+}
+
+impl<'a> From<&'a WCStr> for Cow<'a, WCStr> {
+    fn from(s: &'a WCStr) -> Cow<'a, WCStr> {
+        Cow::Borrowed(s)
+    }
+}
+
+impl<'a> IntoIterator for &'a WCStr {
+    type Item = u16;
+    type IntoIter = CodeUnits<'a>;
+    fn into_iter(self) -> CodeUnits<'a> {
+        self.code_units()
+    }
+}
@@ -1,16 +1,30 @@
 
 use ::std;
-use ::std::ffi::OsStr;
-use ::std::os::windows::ffi::OsStrExt;
+use ::std::borrow::Cow;
+use ::std::ffi::{OsStr, OsString};
 
 use ::error;
-use ::{NulError, NoNulError};
+use ::os;
+use ::{NulError, NoNulError, Utf32Error};
 use ::WCStr;
 use ::split;
-use ::Split;
+use ::{Split, SplitOwned};
+use ::CodeUnits;
+use ::utf16;
+
+/// What lossy operations should do when they encounter a ```nul``` in their input.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NulPolicy {
+    /// Stop at the first ```nul```, ignoring the rest of the input.
+    Truncate,
+    /// Drop every ```nul```, keeping the rest of the input.
+    Skip,
+    /// Replace every ```nul``` with the given (non-```nul```) ```u16```.
+    Replace(u16),
+}
 
 /// A type representing an owned Win32 style "wide" string.
-#[derive(PartialEq, PartialOrd, Eq, Ord, Hash, Clone)]
+#[derive(PartialEq, PartialOrd, Eq, Ord, Clone)]
 pub struct WCString {
     inner: Vec<u16>
 }
@@ -27,13 +41,48 @@ impl WCString {
         }
     }
 
+    /// Create an empty ```WCString``` with room for at least ```capacity``` ```u16```s, not counting the
+    /// ```nul``` terminator.
+    /// # ```with_capacity()``` example
+    ///     use wcstr::WCString;
+    ///     let mut s = WCString::with_capacity(32);
+    ///     assert!(s.len() == 0 && s.capacity() >= 32);
+    ///     s.push_str("app.exe --verbose").unwrap();
+    ///     assert!(s.capacity() >= 32);
+    pub fn with_capacity(capacity: usize) -> WCString {
+        let mut v = Vec::with_capacity(capacity + 1);
+        v.push(0);
+        WCString {
+            inner: v
+        }
+    }
+
+    /// Return the number of ```u16```s the string can hold without reallocating, not counting the
+    /// ```nul``` terminator.
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity() - 1
+    }
+
+    /// Reserve room for at least ```additional``` more ```u16```s.
+    pub fn reserve(&mut self, additional: usize) {
+        self.inner.reserve(additional)
+    }
+
+    /// Reserve room for exactly ```additional``` more ```u16```s.
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.inner.reserve_exact(additional)
+    }
+
+    /// Shrink the capacity as much as possible, keeping the ```nul``` terminator.
+    pub fn shrink_to_fit(&mut self) {
+        self.inner.shrink_to_fit()
+    }
+
     /// Create a ```WCString``` from a ```Vec<u16>```.
     /// The string will be scanned for nul and NulError will be returned if a nul is found.
     /// # ```from_vec()``` example
     ///     use wcstr::WCString;
-    ///     use std::os::windows::ffi::OsStrExt;
-    ///     use std::ffi::OsStr;
-    ///     let v: Vec<_> = OsStr::new("testing").encode_wide().collect();
+    ///     let v: Vec<u16> = "testing".encode_utf16().collect();
     ///     let s = WCString::from_vec(v).unwrap();
     ///     assert!(s.len() == 7);
     pub fn from_vec<T>(v: T) -> Result<WCString, NulError>
@@ -45,15 +94,34 @@ impl WCString {
         }
     }
 
+    /// Create a ```WCString``` from an iterator of ```u16```s in a single pass.
+    /// ```NulError``` will be returned as soon as a nul is found.
+    /// # ```try_from_iter()``` example
+    ///     use wcstr::WCString;
+    ///     let s = WCString::try_from_iter("testing".encode_utf16()).unwrap();
+    ///     assert!(s.len() == 7);
+    ///     let e = WCString::try_from_iter(vec![97u16, 0, 98]).unwrap_err();
+    ///     assert!(e.nul_position() == 1);
+    pub fn try_from_iter<I>(iter: I) -> Result<WCString, NulError>
+        where I: IntoIterator<Item = u16> {
+        let iter = iter.into_iter();
+        let mut v = Vec::with_capacity(iter.size_hint().0 + 1);
+        for w in iter {
+            if w == 0 {
+                return Err(error::nul(v.len(), None));
+            }
+            v.push(w);
+        }
+        Ok(unsafe { WCString::from_vec_unchecked(v) })
+    }
+
     /// Create a ```WCString``` from a ```Vec<u16>``` with a nul terminator.
     /// The string will be scanned for nul.
     /// The string will be truncated at the position where nul is found.
     /// NoNulError will be returned if a nul could not be found.
     /// # ```from_vec_with_nul()``` example
     ///     use wcstr::WCString;
-    ///     use std::os::windows::ffi::OsStrExt;
-    ///     use std::ffi::OsStr;
-    ///     let v: Vec<_> = OsStr::new("testing\0").encode_wide().collect();
+    ///     let v: Vec<u16> = "testing\0".encode_utf16().collect();
     ///     let s = WCString::from_vec_with_nul(v).unwrap();
     ///     assert!(s.len() == 7);
     pub fn from_vec_with_nul<T>(u16s: T) -> Result<WCString, NoNulError>
@@ -68,16 +136,95 @@ impl WCString {
         }
     }
 
+    /// Create a ```WCString``` from a ```Vec<u16>```, truncating it at the first ```nul```.
+    /// Unlike ```from_vec()```, this never fails: everything from the first ```nul``` onwards is discarded.
+    /// This is useful for fixed-size buffers that are padded with garbage after the terminator.
+    /// # ```from_vec_truncate()``` example
+    ///     use wcstr::WCString;
+    ///     let s = WCString::from_vec_truncate(vec![116u16, 101, 0, 0xCCCC, 0xCCCC]);
+    ///     assert!(s.len() == 2);
+    ///     let t = WCString::from_vec_truncate(vec![116u16, 101]);
+    ///     assert!(t.len() == 2);
+    pub fn from_vec_truncate<T>(v: T) -> WCString
+        where T: Into<Vec<u16>> {
+        let mut v = v.into();
+        if let Some(i) = v.iter().position(|&x| x == 0) {
+            v.truncate(i);
+        }
+        unsafe { WCString::from_vec_unchecked(v) }
+    }
+
+    /// Create a ```WCString``` from an iterator of ```char```s.
+    /// NulError will be returned if a ```'\0'``` is found, with its position in ```char```s.
+    /// # ```from_chars()``` example
+    ///     use wcstr::WCString;
+    ///     let s = WCString::from_chars(vec!['a', '\u{1F600}']).unwrap();
+    ///     assert!(s.len() == 3);
+    pub fn from_chars<I>(chars: I) -> Result<WCString, NulError>
+        where I: IntoIterator<Item = char> {
+        let mut v = Vec::new();
+        for (i, c) in chars.into_iter().enumerate() {
+            if c == '\0' {
+                return Err(error::nul(i, None));
+            }
+            let mut buf = [0u16; 2];
+            v.extend_from_slice(c.encode_utf16(&mut buf));
+        }
+        Ok(unsafe { WCString::from_vec_unchecked(v) })
+    }
+
+    /// Create a ```WCString``` from UTF-32 code units, encoding code points above ```U+FFFF``` as surrogate pairs.
+    /// ```Utf32Error``` will be returned for the first code unit that is nul, a surrogate, or above ```U+10FFFF```.
+    /// # ```from_utf32()``` example
+    ///     use wcstr::WCString;
+    ///     let s = WCString::from_utf32(&[0x61, 0x1F600]).unwrap();
+    ///     assert!(s.to_slice() == [0x61, 0xD83D, 0xDE00]);
+    ///     let e = WCString::from_utf32(&[0x61, 0xD800]).unwrap_err();
+    ///     assert!(e.position() == 1 && e.value() == 0xD800);
+    pub fn from_utf32<T>(units: T) -> Result<WCString, Utf32Error>
+        where T: AsRef<[u32]> {
+        let units = units.as_ref();
+        let mut v = Vec::with_capacity(units.len() + 1);
+        for (i, &u) in units.iter().enumerate() {
+            match std::char::from_u32(u) {
+                Some(c) if c != '\0' => {
+                    let mut buf = [0u16; 2];
+                    v.extend_from_slice(c.encode_utf16(&mut buf));
+                },
+                _ => return Err(error::utf32(i, u)),
+            }
+        }
+        Ok(unsafe { WCString::from_vec_unchecked(v) })
+    }
+
+    /// Create a ```WCString``` from an iterator of ```char```s, handling any ```'\0'``` according to ```policy```.
+    /// This never fails.
+    ///
+    /// * This will assert if ```policy``` is ```NulPolicy::Replace(0)```.
+    ///
+    /// # ```from_chars_lossy()``` example
+    ///     use wcstr::{WCString, NulPolicy};
+    ///     let s = WCString::from_chars_lossy("a\0b".chars(), NulPolicy::Skip);
+    ///     assert!(s.to_string().unwrap() == "ab");
+    ///     let t = WCString::from_chars_lossy("a\0b".chars(), NulPolicy::Replace(0xFFFD));
+    ///     assert!(t.to_string().unwrap() == "a\u{FFFD}b");
+    pub fn from_chars_lossy<I>(chars: I, policy: NulPolicy) -> WCString
+        where I: IntoIterator<Item = char> {
+        let mut s = WCString::new();
+        s.push_units_lossy(utf16::encode(chars.into_iter()), policy);
+        s
+    }
+
     /// Create a ```WCString``` from a ```Vec<u16>``` without checking for validity.
     /// This function is unsafe as it assumes that the string passed in has no nul in it.
+    /// With the ```extra-checks``` feature, debug builds verify this and panic if a nul is found.
     /// # ```from_vec_unchecked()``` example
     ///     use wcstr::WCString;
-    ///     use std::os::windows::ffi::OsStrExt;
-    ///     use std::ffi::OsStr;
-    ///     let v: Vec<_> = OsStr::new("testing").encode_wide().collect();
+    ///     let v: Vec<u16> = "testing".encode_utf16().collect();
     ///     let s = unsafe { WCString::from_vec_unchecked(v) };
     ///     assert!(s.len() == 7);
     pub unsafe fn from_vec_unchecked(v: Vec<u16>) -> WCString {
+        error::check_no_nul("WCString::from_vec_unchecked", &v);
         let mut v = v;
         v.push(0);
         WCString::from_vec_with_nul_unchecked(v)
@@ -86,16 +233,17 @@ impl WCString {
     /// Create a ```WCString``` from a ```Vec<u16>``` with a nul terminator without checking for validity.
     /// This function is unsafe for the following reasons:
     ///  * This function assumes that the string passed in has no nul in it aside from the nul
-    ///  terminator.
+    ///    terminator.
     ///  * This function assumes that the string passed in has a nul terminator at the end.
+    ///
+    /// With the ```extra-checks``` feature, debug builds verify this and panic if either assumption is wrong.
     /// # ```from_vec_with_nul_unchecked()``` example
     ///     use wcstr::WCString;
-    ///     use std::os::windows::ffi::OsStrExt;
-    ///     use std::ffi::OsStr;
-    ///     let v: Vec<_> = OsStr::new("testing\0").encode_wide().collect();
+    ///     let v: Vec<u16> = "testing\0".encode_utf16().collect();
     ///     let s = unsafe { WCString::from_vec_with_nul_unchecked(v) };
     ///     assert!(s.len() == 7);
     pub unsafe fn from_vec_with_nul_unchecked(v: Vec<u16>) -> WCString {
+        error::check_nul_terminated("WCString::from_vec_with_nul_unchecked", &v);
         WCString { inner: v }
     }
 
@@ -107,7 +255,7 @@ impl WCString {
     ///     assert!(s.len() == 7);
     pub fn from_str<T>(s: T) -> Result<WCString, NulError>
         where T: AsRef<OsStr> {
-        let v: Vec<u16> = s.as_ref().encode_wide().collect();
+        let v: Vec<u16> = os::encode_wide(s.as_ref()).collect();
         WCString::from_vec(v)
     }
 
@@ -120,10 +268,23 @@ impl WCString {
     ///     assert!(s.len() == 7);
     pub fn from_str_with_nul<T>(s: T) -> Result<WCString, NoNulError>
         where T: AsRef<OsStr> {
-        let v: Vec<u16> = s.as_ref().encode_wide().collect();
+        let v: Vec<u16> = os::encode_wide(s.as_ref()).collect();
         WCString::from_vec_with_nul(v)
     }
 
+    /// Create a ```WCString``` from a ```&OsStr``` (or anything that can be cast to ```&OsStr```), truncating it at the
+    /// first ```nul```.
+    /// Unlike ```from_str()```, this never fails: everything from the first ```nul``` onwards is discarded.
+    /// # ```from_str_truncate()``` example
+    ///     use wcstr::WCString;
+    ///     let s = WCString::from_str_truncate("testing\0garbage");
+    ///     assert!(s.len() == 7);
+    pub fn from_str_truncate<T>(s: T) -> WCString
+        where T: AsRef<OsStr> {
+        let v: Vec<u16> = os::encode_wide(s.as_ref()).take_while(|&w| w != 0).collect();
+        unsafe { WCString::from_vec_unchecked(v) }
+    }
+
 
     /// Return the underlying buffer as a ```Vec<u16>```.
     ///
@@ -154,6 +315,44 @@ impl WCString {
         self.inner
     }
 
+    /// Transfer ownership of the string to a raw pointer to its ```nul``` terminated contents, for handing
+    /// it to C code that will later give it back.
+    ///
+    /// * The buffer is shrunk to exactly ```len() + 1``` ```u16```s and allocated by Rust's global allocator,
+    ///   so it must not be freed with ```free()```, ```LocalFree()``` or ```CoTaskMemFree()```.
+    /// * The only way to release it is to pass the pointer back to ```WCString::from_raw()```, exactly once.
+    /// * C code may modify the contents, but must not write a ```nul``` before the terminator or
+    ///   otherwise change the length, because ```from_raw()``` recomputes the allocation size from it.
+    ///
+    /// # ```into_raw()``` example
+    ///     use wcstr::WCString;
+    ///     let s = WCString::from_str("testing").unwrap();
+    ///     let ptr = s.into_raw();
+    ///     // ... hand ptr to C code, and get it back later ...
+    ///     let s = unsafe { WCString::from_raw(ptr) };
+    ///     assert!(s == "testing");
+    pub fn into_raw(self) -> *mut u16 {
+        Box::into_raw(self.inner.into_boxed_slice()) as *mut u16
+    }
+
+    /// Retake ownership of a string transferred with ```into_raw()```.
+    ///
+    /// ```ptr``` must have been returned by ```into_raw()```, must not have been passed to ```from_raw()```
+    /// before, and its length must not have changed (see ```into_raw()```).
+    pub unsafe fn from_raw(ptr: *mut u16) -> WCString {
+        let len = WCStr::from_ptr(ptr).len();
+        let slice: *mut [u16] = std::ptr::slice_from_raw_parts_mut(ptr, len + 1);
+        WCString {
+            inner: Box::from_raw(slice).into_vec()
+        }
+    }
+
+    /// Convert this string into a ```Box<WCStr>```, dropping any excess capacity.
+    pub fn into_boxed_wcstr(self) -> Box<WCStr> {
+        let b: Box<[u16]> = self.inner.into_boxed_slice();
+        unsafe { Box::from_raw(Box::into_raw(b) as *mut WCStr) }
+    }
+
     /// Return the underlying buffer as a ```u16``` slice.
     ///
     /// * The returned slice does not contain the ```nul``` terminator.
@@ -178,13 +377,38 @@ impl WCString {
         &self.inner
     }
 
+    /// Return a mutable reference to the underlying buffer, including the ```nul``` terminator.
+    ///
+    /// This function is unsafe as the caller must make sure that, by the time the ```WCString``` is used
+    /// again, the buffer ends with a ```nul``` terminator and contains no other ```nul```.
+    /// # ```as_mut_vec()``` example
+    ///     use wcstr::WCString;
+    ///     let mut s = WCString::from_str("a/b/c").unwrap();
+    ///     unsafe {
+    ///         let v = s.as_mut_vec();
+    ///         for w in v.iter_mut().filter(|w| **w == b'/' as u16) {
+    ///             *w = b'\\' as u16;
+    ///         }
+    ///     }
+    ///     assert!(s.to_string().unwrap() == "a\\b\\c");
+    pub unsafe fn as_mut_vec(&mut self) -> &mut Vec<u16> {
+        &mut self.inner
+    }
+
     /// Return this string as a ```&WCStr```
     /// # ```as_wcstr()``` example
     ///     use wcstr::WCString;
     ///     let s = WCString::from_str("testing").unwrap();
     ///     let w = s.as_wcstr();
     pub fn as_wcstr(&self) -> &WCStr {
-        &self
+        self
+    }
+
+    /// Convert this "wide" string to a ```String``` by using ```String::from_utf16```.
+    /// This is ```WCStr::to_string()```, which would otherwise be hidden by ```ToString```; use
+    /// ```format!``` for the lossy ```Display``` conversion.
+    pub fn to_string(&self) -> Result<String, std::string::FromUtf16Error> {
+        self.as_wcstr().to_string()
     }
 
     /// Push/Append a ```&WCStr``` (or anything that can cast to a ```&WCStr```, like another ```WCString```).
@@ -225,6 +449,42 @@ impl WCString {
         }
     }
 
+    /// Push/Append a ```char```, encoding it as a surrogate pair if needed.
+    ///
+    /// * This will assert if ```c``` is ```nul```.
+    ///
+    /// # ```push_char()``` example
+    ///     use wcstr::WCString;
+    ///     let mut s = WCString::new();
+    ///     s.push_char('a');
+    ///     s.push_char('\u{1F600}');
+    ///     assert!(s.as_slice() == [0x61, 0xD83D, 0xDE00]);
+    pub fn push_char(&mut self, c: char) {
+        assert!(c != '\0');
+        let mut buf = [0u16; 2];
+        let _nul = self.inner.pop();
+        debug_assert_eq!(_nul, Some(0u16));
+        self.inner.extend_from_slice(c.encode_utf16(&mut buf));
+        self.inner.push(0);
+    }
+
+    /// Push/Append a single ```u16```, failing with ```NulError``` if it is ```nul```.
+    /// # ```push_u16()``` example
+    ///     use wcstr::WCString;
+    ///     let mut s = WCString::new();
+    ///     s.push_u16(b'a' as u16).unwrap();
+    ///     assert!(s.push_u16(0).is_err());
+    ///     assert!(s == "a");
+    pub fn push_u16(&mut self, w: u16) -> Result<(), NulError> {
+        if w == 0 {
+            return Err(error::nul(0, None));
+        }
+        let len = self.inner.len();
+        self.inner[len - 1] = w;
+        self.inner.push(0);
+        Ok(())
+    }
+
     /// Push/Append a ```u16``` slice with a ```nul``` terminator.
     /// The slice will be scanned for ```nul```, and the push will fail with ```NoNulError``` if a nul is not
     /// found.
@@ -265,7 +525,7 @@ impl WCString {
         let len = self.inner.len();
         let s = s.as_ref();
         let mut not_nuled = true;
-        self.inner.extend(s.encode_wide().take_while(|&w| { not_nuled = w != 0; not_nuled }));
+        self.inner.extend(os::encode_wide(s).take_while(|&w| { not_nuled = w != 0; not_nuled }));
 
         if not_nuled {
             self.inner.push(0);
@@ -286,7 +546,7 @@ impl WCString {
         debug_assert_eq!(_nul, Some(0u16));
 
         let s = s.as_ref();
-        self.inner.extend(s.encode_wide());
+        self.inner.extend(os::encode_wide(s));
         self.inner.push(0);
     }
 
@@ -307,7 +567,7 @@ impl WCString {
         let len = self.inner.len();
         let s = s.as_ref();
         let mut not_nuled = true;
-        self.inner.extend(s.encode_wide().take_while(|&w| { not_nuled = w != 0; not_nuled }));
+        self.inner.extend(os::encode_wide(s).take_while(|&w| { not_nuled = w != 0; not_nuled }));
         if not_nuled {
             self.inner.truncate(len);
             self.inner.push(0);
@@ -319,8 +579,208 @@ impl WCString {
         }
     }
 
+    /// Insert a ```char``` at a ```u16``` offset, shifting the rest of the string only once.
+    ///
+    /// * This will assert if ```idx``` is not on a character boundary (see ```is_char_boundary()```).
+    /// * This will assert if ```c``` is ```nul```.
+    ///
+    /// # ```insert_char()``` example
+    ///     use wcstr::WCString;
+    ///     let mut s = WCString::from_str("ac").unwrap();
+    ///     s.insert_char(1, 'b');
+    ///     s.insert_char(3, '\u{1F600}');
+    ///     assert!(s.to_string().unwrap() == "abc\u{1F600}");
+    pub fn insert_char(&mut self, idx: usize, c: char) {
+        assert!(self.is_char_boundary(idx));
+        assert!(c != '\0');
+        let mut buf = [0u16; 2];
+        let units = c.encode_utf16(&mut buf);
+        self.inner.splice(idx..idx, units.iter().cloned());
+    }
+
+    /// Remove and return the ```char``` at a ```u16``` offset, removing both units of a surrogate pair.
+    /// An unpaired surrogate is removed on its own and returned as ```U+FFFD REPLACEMENT CHARACTER```.
+    ///
+    /// * This will assert if ```idx``` is not on a character boundary, or is at the end of the string.
+    ///
+    /// # ```remove()``` example
+    ///     use wcstr::WCString;
+    ///     let mut s = WCString::from_str("a\u{1F600}b").unwrap();
+    ///     assert!(s.remove(1) == '\u{1F600}');
+    ///     assert!(s == "ab");
+    pub fn remove(&mut self, idx: usize) -> char {
+        assert!(idx < self.len() && self.is_char_boundary(idx));
+        let (c, units) = match std::char::decode_utf16(self.inner[idx..].iter().cloned()).next() {
+            Some(Ok(c)) => (c, c.len_utf16()),
+            _ => (std::char::REPLACEMENT_CHARACTER, 1),
+        };
+        self.inner.drain(idx..idx + units);
+        c
+    }
+
+    fn char_range<R>(&self, range: R) -> std::ops::Range<usize>
+        where R: std::ops::RangeBounds<usize> {
+        use std::ops::Bound;
+        let start = match range.start_bound() {
+            Bound::Included(&i) => i,
+            Bound::Excluded(&i) => i.checked_add(1).expect("attempted to index from after maximum usize"),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&i) => i.checked_add(1).expect("attempted to index to after maximum usize"),
+            Bound::Excluded(&i) => i,
+            Bound::Unbounded => self.len(),
+        };
+        assert!(start <= end && end <= self.len());
+        assert!(self.is_char_boundary(start) && self.is_char_boundary(end));
+        start..end
+    }
+
+    /// Remove a range of ```u16```s, returning them as an iterator.
+    /// The range is removed even if the iterator is not consumed.
+    ///
+    /// * This will assert if the range is out of bounds, or does not start and end on character boundaries.
+    ///
+    /// # ```drain()``` example
+    ///     use wcstr::WCString;
+    ///     let mut s = WCString::from_str("app.exe --verbose").unwrap();
+    ///     let flag: Vec<u16> = s.drain(7..).collect();
+    ///     assert!(s == "app.exe");
+    ///     assert!(flag.len() == 10);
+    pub fn drain<'a, R>(&'a mut self, range: R) -> std::vec::Drain<'a, u16>
+        where R: std::ops::RangeBounds<usize> {
+        let range = self.char_range(range);
+        self.inner.drain(range)
+    }
+
+    /// Replace a range of ```u16```s with a ```&WCStr``` (or anything that can be cast to ```&WCStr```).
+    ///
+    /// * This will assert if the range is out of bounds, or does not start and end on character boundaries.
+    ///
+    /// # ```replace_range()``` example
+    ///     use wcstr::WCString;
+    ///     let mut s = WCString::from_str("C:\\Temp\\log.txt").unwrap();
+    ///     s.replace_range(3..7, WCString::from_str("Logs").unwrap());
+    ///     assert!(s == "C:\\Logs\\log.txt");
+    pub fn replace_range<R, T>(&mut self, range: R, replacement: T)
+        where R: std::ops::RangeBounds<usize>, T: AsRef<WCStr> {
+        let range = self.char_range(range);
+        self.inner.splice(range, replacement.as_ref().to_slice().iter().cloned());
+    }
+
+    /// Split the string in two at a ```u16``` offset, returning the tail and keeping the head.
+    ///
+    /// * This will assert if ```at``` is out of bounds or not on a character boundary.
+    ///
+    /// # ```split_off()``` example
+    ///     use wcstr::WCString;
+    ///     let mut s = WCString::from_str("C:\\Windows").unwrap();
+    ///     let rest = s.split_off(2);
+    ///     assert!(s == "C:");
+    ///     assert!(rest == "\\Windows");
+    pub fn split_off(&mut self, at: usize) -> WCString {
+        assert!(at <= self.len() && self.is_char_boundary(at));
+        let tail = self.inner.split_off(at);
+        self.inner.push(0);
+        unsafe { WCString::from_vec_with_nul_unchecked(tail) }
+    }
+
+    /// Keep only the ```char```s for which ```f``` returns ```true```, removing the rest in place.
+    /// Unpaired surrogates are passed to ```f``` as ```U+FFFD REPLACEMENT CHARACTER```.
+    ///
+    /// # ```retain()``` example
+    ///     use wcstr::WCString;
+    ///     let mut s = WCString::from_str("re<port>?\u{1F600}.txt").unwrap();
+    ///     s.retain(|c| !"<>:\"/\\|?*".contains(c));
+    ///     assert!(s == "report\u{1F600}.txt");
+    pub fn retain<F>(&mut self, mut f: F)
+        where F: FnMut(char) -> bool {
+        let len = self.len();
+        let (mut read, mut write) = (0, 0);
+        while read < len {
+            let (c, units) = match std::char::decode_utf16(self.inner[read..len].iter().cloned()).next() {
+                Some(Ok(c)) => (c, c.len_utf16()),
+                _ => (std::char::REPLACEMENT_CHARACTER, 1),
+            };
+            if f(c) {
+                self.inner.copy_within(read..read + units, write);
+                write += units;
+            }
+            read += units;
+        }
+        self.truncate(write);
+    }
+
+    /// Keep only the ```u16```s for which ```f``` returns ```true```, removing the rest in place.
+    ///
+    /// # ```retain_units()``` example
+    ///     use wcstr::WCString;
+    ///     let mut s = WCString::from_str("line\r\nbreak").unwrap();
+    ///     s.retain_units(|w| w >= 0x20);
+    ///     assert!(s == "linebreak");
+    pub fn retain_units<F>(&mut self, mut f: F)
+        where F: FnMut(u16) -> bool {
+        self.inner.retain(|&w| w == 0 || f(w));
+    }
+
+    /// Push/Append a ```u16``` slice, handling any ```nul``` in it according to ```policy```.
+    /// This never fails, and returns the number of ```u16```s pushed.
+    ///
+    /// * This will assert if ```policy``` is ```NulPolicy::Replace(0)```.
+    ///
+    /// # ```push_slice_lossy()``` example
+    ///     use wcstr::{WCString, NulPolicy};
+    ///     let mut s = WCString::new();
+    ///     assert!(s.push_slice_lossy(&[97u16, 0, 98], NulPolicy::Truncate) == 1);
+    ///     assert!(s.push_slice_lossy(&[97u16, 0, 98], NulPolicy::Replace(b'?' as u16)) == 3);
+    ///     assert!(s.to_string().unwrap() == "aa?b");
+    pub fn push_slice_lossy<T>(&mut self, s: T, policy: NulPolicy) -> usize
+        where T: AsRef<[u16]> {
+        self.push_units_lossy(s.as_ref().iter().cloned(), policy)
+    }
+
+    /// Push/Append a ```&OsStr``` (or anything that can be cast to ```&OsStr```), handling any ```nul``` in it
+    /// according to ```policy```.
+    /// This never fails, and returns the number of ```u16```s pushed.
+    ///
+    /// * This will assert if ```policy``` is ```NulPolicy::Replace(0)```.
+    ///
+    /// # ```push_str_lossy()``` example
+    ///     use wcstr::{WCString, NulPolicy};
+    ///     let mut s = WCString::new();
+    ///     assert!(s.push_str_lossy("log\0line", NulPolicy::Replace(b' ' as u16)) == 8);
+    ///     assert!(s.push_str_lossy("!\0ignored", NulPolicy::Truncate) == 1);
+    ///     assert!(s.to_string().unwrap() == "log line!");
+    pub fn push_str_lossy<T>(&mut self, s: T, policy: NulPolicy) -> usize
+        where T: AsRef<OsStr> {
+        self.push_units_lossy(os::encode_wide(s.as_ref()), policy)
+    }
+
+    fn push_units_lossy<I>(&mut self, units: I, policy: NulPolicy) -> usize
+        where I: Iterator<Item = u16> {
+        assert!(policy != NulPolicy::Replace(0));
+        let _nul = self.inner.pop();
+        debug_assert_eq!(_nul, Some(0u16));
+
+        let len = self.inner.len();
+        match policy {
+            NulPolicy::Truncate => self.inner.extend(units.take_while(|&w| w != 0)),
+            NulPolicy::Skip => self.inner.extend(units.filter(|&w| w != 0)),
+            NulPolicy::Replace(r) => self.inner.extend(units.map(|w| if w == 0 { r } else { w })),
+        }
+        let pushed = self.inner.len() - len;
+        self.inner.push(0);
+        pushed
+    }
+
     /// Truncate the string to a specified length. If the string was shorter than the specified
     /// length, this has no effect.
+    /// # ```truncate()``` example
+    ///     use wcstr::WCString;
+    ///     let mut s = WCString::from_str("name=value").unwrap();
+    ///     s.truncate(4);
+    ///     assert!(s == "name");
+    ///     assert!(s.as_slice_with_nul().len() == 5);
     pub fn truncate(&mut self, len: usize) {
         if (self.inner.len() - 1) > len {
             self.inner.truncate(len);
@@ -328,10 +788,54 @@ impl WCString {
         }
     }
 
+    /// Remove everything from the string, keeping its capacity.
+    /// # ```clear()``` example
+    ///     use wcstr::WCString;
+    ///     let mut s = WCString::from_str("testing").unwrap();
+    ///     s.clear();
+    ///     assert!(s.is_empty());
+    ///     assert!(s.as_slice_with_nul() == [0]);
+    pub fn clear(&mut self) {
+        self.truncate(0)
+    }
+
+    /// Remove and return the last ```u16```, or ```None``` if the string is empty.
+    /// # ```pop()``` example
+    ///     use wcstr::WCString;
+    ///     let mut s = WCString::from_str("C:\\").unwrap();
+    ///     assert!(s.pop() == Some(b'\\' as u16));
+    ///     assert!(s == "C:");
+    pub fn pop(&mut self) -> Option<u16> {
+        if self.inner.len() > 1 {
+            self.inner.pop();
+            let w = self.inner.pop();
+            self.inner.push(0);
+            w
+        }
+        else {
+            None
+        }
+    }
+
+    /// Truncate the string to at most ```max_units``` ```u16```s without splitting a surrogate pair.
+    ///
+    /// If truncating at ```max_units``` would cut a surrogate pair in half, the whole pair is
+    /// removed. If the string was shorter than ```max_units```, this has no effect.
+    ///
+    /// # ```truncate_at_char_boundary()``` example
+    ///     use wcstr::WCString;
+    ///     let mut s = WCString::from_str("ab\u{1F600}").unwrap();
+    ///     s.truncate_at_char_boundary(3);
+    ///     assert!(s.len() == 2);
+    pub fn truncate_at_char_boundary(&mut self, max_units: usize) {
+        let len = self.floor_char_boundary(max_units);
+        self.truncate(len);
+    }
+
     /// Split the string into multiple ```&mut WCStr``` using a delimiter.
     ///
     /// * This returns an iterator that creates a ```&mut WCStr``` for each part of the string
-    /// separated by the delimiter.
+    ///   separated by the delimiter.
     /// * This will consume the string.
     ///
     /// # ```split()``` example
@@ -347,6 +851,22 @@ impl WCString {
         split::new(self.inner, delimiter)
     }
 
+    /// Split the string into multiple owned ```WCString```s using a delimiter.
+    ///
+    /// * Unlike ```split()```, the items are independent of the iterator and can outlive it.
+    /// * Each item is allocated with exactly the capacity it needs.
+    /// * This will consume the string.
+    ///
+    /// # ```split_owned()``` example
+    ///     use wcstr::WCString;
+    ///     let s = WCString::from_str("C:\\bin;D:\\tools").unwrap();
+    ///     let parts: Vec<WCString> = s.split_owned(b';' as u16).collect();
+    ///     assert!(parts.len() == 2);
+    ///     assert!(parts[1].to_string().unwrap() == "D:\\tools");
+    pub fn split_owned(self, delimiter: u16) -> SplitOwned {
+        split::new_owned(self.inner, delimiter)
+    }
+
     /// Replace a ```u16``` value with another ```u16``` value in the string.
     ///
     /// * This will assert if either ```needle``` or ```replacement``` is ```nul```
@@ -366,6 +886,32 @@ impl WCString {
             }
         }
     }
+
+    /// Convert ASCII letters to uppercase in place, leaving every other unit unchanged.
+    ///
+    /// # ```make_ascii_uppercase()``` example
+    ///     use wcstr::WCString;
+    ///     let mut s = WCString::from_str("c:\\straße").unwrap();
+    ///     s.make_ascii_uppercase();
+    ///     assert!(s == "C:\\STRAßE");
+    pub fn make_ascii_uppercase(&mut self) {
+        for w in self.inner.iter_mut() {
+            *w = utf16::to_ascii_uppercase(*w);
+        }
+    }
+
+    /// Convert ASCII letters to lowercase in place, leaving every other unit unchanged.
+    ///
+    /// # ```make_ascii_lowercase()``` example
+    ///     use wcstr::WCString;
+    ///     let mut s = WCString::from_str("GET /ÄPFEL").unwrap();
+    ///     s.make_ascii_lowercase();
+    ///     assert!(s == "get /Äpfel");
+    pub fn make_ascii_lowercase(&mut self) {
+        for w in self.inner.iter_mut() {
+            *w = utf16::to_ascii_lowercase(*w);
+        }
+    }
 }
 
 impl std::ops::Deref for WCString {
@@ -378,7 +924,240 @@ impl std::ops::Deref for WCString {
 
 impl std::fmt::Debug for WCString {
     fn fmt(&self, formatter: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
-        std::fmt::Debug::fmt(&self, formatter)
+        std::fmt::Debug::fmt(self.as_wcstr(), formatter)
+    }
+}
+
+impl std::fmt::Display for WCString {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        std::fmt::Display::fmt(self.as_wcstr(), formatter)
+    }
+}
+
+impl From<Box<WCStr>> for WCString {
+    fn from(b: Box<WCStr>) -> WCString {
+        let b: Box<[u16]> = unsafe { Box::from_raw(Box::into_raw(b) as *mut [u16]) };
+        WCString { inner: b.into_vec() }
+    }
+}
+
+impl From<WCString> for Box<WCStr> {
+    fn from(s: WCString) -> Box<WCStr> {
+        s.into_boxed_wcstr()
+    }
+}
+
+impl From<WCString> for std::sync::Arc<WCStr> {
+    fn from(s: WCString) -> std::sync::Arc<WCStr> {
+        std::sync::Arc::from(s.as_wcstr())
+    }
+}
+
+impl From<WCString> for std::rc::Rc<WCStr> {
+    fn from(s: WCString) -> std::rc::Rc<WCStr> {
+        std::rc::Rc::from(s.as_wcstr())
+    }
+}
+
+/// # ```Cow<WCStr>``` example
+///
+///     use std::borrow::Cow;
+///     use wcstr::{WCStr, WCString};
+///     fn class_name<'a, T: Into<Cow<'a, WCStr>>>(name: T) -> WCString {
+///         name.into().into()
+///     }
+///     let s = WCString::from_str("MyWindowClass").unwrap();
+///     assert!(class_name(&*s) == s);
+///     assert!(class_name(&s) == s);
+///     assert!(class_name(s.clone()) == s);
+impl<'a> From<WCString> for Cow<'a, WCStr> {
+    fn from(s: WCString) -> Cow<'a, WCStr> {
+        Cow::Owned(s)
+    }
+}
+
+impl<'a> From<&'a WCString> for Cow<'a, WCStr> {
+    fn from(s: &'a WCString) -> Cow<'a, WCStr> {
+        Cow::Borrowed(s.as_wcstr())
+    }
+}
+
+impl<'a> From<Cow<'a, WCStr>> for WCString {
+    fn from(s: Cow<'a, WCStr>) -> WCString {
+        s.into_owned()
+    }
+}
+
+/// # ```FromStr``` example
+///
+///     use wcstr::WCString;
+///     let s: WCString = "testing".parse().unwrap();
+///     assert!(s == "testing");
+///     assert!("nul\0".parse::<WCString>().unwrap_err().nul_position() == 3);
+impl std::str::FromStr for WCString {
+    type Err = NulError;
+
+    fn from_str(s: &str) -> Result<WCString, NulError> {
+        WCString::from_str(s)
+    }
+}
+
+macro_rules! impl_try_from_str {
+    ($($t:ty),*) => {
+        $(
+            impl<'a> std::convert::TryFrom<$t> for WCString {
+                type Error = NulError;
+
+                fn try_from(s: $t) -> Result<WCString, NulError> {
+                    WCString::from_str(s)
+                }
+            }
+        )*
+    }
+}
+
+/// # ```TryFrom``` example
+///
+///     use std::convert::TryFrom;
+///     use std::ffi::OsString;
+///     use wcstr::WCString;
+///     let s = WCString::try_from("testing").unwrap();
+///     assert!(s == "testing");
+///     let s = WCString::try_from(OsString::from("testing")).unwrap();
+///     assert!(s == "testing");
+///     assert!(WCString::try_from(String::from("nul\0")).is_err());
+impl<'a> std::convert::TryFrom<&'a str> for WCString {
+    type Error = NulError;
+
+    fn try_from(s: &'a str) -> Result<WCString, NulError> {
+        WCString::from_str(s)
+    }
+}
+
+impl_try_from_str!(String, &'a OsStr, OsString);
+
+/// Same as ```from_vec()```.
+///
+/// # ```TryFrom<Vec<u16>>``` example
+///
+///     use std::convert::TryFrom;
+///     use wcstr::WCString;
+///     let s = WCString::try_from(vec![b'a' as u16, b'b' as u16]).unwrap();
+///     assert!(s == "ab");
+///     let e = WCString::try_from(vec![b'a' as u16, 0]).unwrap_err();
+///     assert!(e.into_vec() == Some(vec![b'a' as u16, 0]));
+impl std::convert::TryFrom<Vec<u16>> for WCString {
+    type Error = NulError;
+
+    fn try_from(v: Vec<u16>) -> Result<WCString, NulError> {
+        WCString::from_vec(v)
+    }
+}
+
+impl Default for WCString {
+    fn default() -> WCString {
+        WCString::new()
+    }
+}
+
+/// Collect ```u16```s into a ```WCString```, stopping at the first ```nul```.
+/// Use ```try_from_iter()``` to get a ```NulError``` instead.
+///
+/// # ```FromIterator<u16>``` example
+///
+///     use wcstr::WCString;
+///     let s: WCString = "a-b-c".encode_utf16().filter(|&w| w != b'-' as u16).collect();
+///     assert!(s == "abc");
+///     let s: WCString = vec![b'a' as u16, 0, b'b' as u16].into_iter().collect();
+///     assert!(s == "a");
+impl std::iter::FromIterator<u16> for WCString {
+    fn from_iter<I: IntoIterator<Item = u16>>(iter: I) -> WCString {
+        let mut s = WCString::new();
+        s.extend(iter);
+        s
+    }
+}
+
+/// Collect ```char```s into a ```WCString```, stopping at the first ```'\0'```.
+/// Use ```from_chars()``` to get a ```NulError``` instead.
+///
+/// # ```FromIterator<char>``` example
+///
+///     use wcstr::WCString;
+///     let s: WCString = "Report?.txt".chars().filter(|&c| c != '?').collect();
+///     assert!(s == "Report.txt");
+impl std::iter::FromIterator<char> for WCString {
+    fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> WCString {
+        let mut s = WCString::new();
+        s.extend(iter);
+        s
+    }
+}
+
+/// Append ```u16```s, stopping at the first ```nul```.
+///
+/// # ```Extend``` example
+///
+///     use wcstr::{WCStr, WCString};
+///     let mut s = WCString::from_str("a").unwrap();
+///     s.extend("bc".encode_utf16());
+///     s.extend("d\0e".chars());
+///     s.extend(vec![WCStr::empty(), &WCString::from_str("fg").unwrap()]);
+///     assert!(s == "abcdfg");
+impl Extend<u16> for WCString {
+    fn extend<I: IntoIterator<Item = u16>>(&mut self, iter: I) {
+        let _nul = self.inner.pop();
+        debug_assert_eq!(_nul, Some(0u16));
+        self.inner.extend(iter.into_iter().take_while(|&w| w != 0));
+        self.inner.push(0);
+    }
+}
+
+/// Append ```char```s, stopping at the first ```'\0'```.
+impl Extend<char> for WCString {
+    fn extend<I: IntoIterator<Item = char>>(&mut self, iter: I) {
+        self.extend(utf16::encode(iter.into_iter().take_while(|&c| c != '\0')));
+    }
+}
+
+impl<'a> Extend<&'a WCStr> for WCString {
+    fn extend<I: IntoIterator<Item = &'a WCStr>>(&mut self, iter: I) {
+        let _nul = self.inner.pop();
+        debug_assert_eq!(_nul, Some(0u16));
+        for s in iter {
+            self.inner.extend_from_slice(s.to_slice());
+        }
+        self.inner.push(0);
+    }
+}
+
+/// Append formatted text, encoding it to UTF-16 on the fly.
+/// Writing a string that contains ```'\0'``` fails with ```fmt::Error```, leaving the ```WCString``` unchanged
+/// by that write.
+///
+/// # ```fmt::Write``` example
+///
+///     use std::fmt::Write;
+///     use wcstr::WCString;
+///     let mut s = WCString::new();
+///     write!(s, "{}:{}", "localhost", 8080).unwrap();
+///     assert!(s == "localhost:8080");
+///     assert!(write!(s, "{}", "\0").is_err());
+impl std::fmt::Write for WCString {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        if s.contains('\0') {
+            return Err(std::fmt::Error);
+        }
+        self.extend(s.encode_utf16());
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> std::fmt::Result {
+        if c == '\0' {
+            return Err(std::fmt::Error);
+        }
+        self.push_char(c);
+        Ok(())
     }
 }
 
@@ -401,3 +1180,159 @@ impl std::borrow::Borrow<WCStr> for WCString {
     }
 }
 
+
+impl std::borrow::Borrow<[u16]> for WCString {
+    fn borrow(&self) -> &[u16] {
+        self.as_slice()
+    }
+}
+
+impl std::hash::Hash for WCString {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.as_wcstr().hash(state)
+    }
+}
+
+macro_rules! impl_eq_str {
+    ($($t:ty),*) => {
+        $(
+            impl<'a> PartialEq<$t> for WCString {
+                fn eq(&self, other: &$t) -> bool {
+                    PartialEq::eq(self.as_wcstr(), other)
+                }
+            }
+        )*
+    }
+}
+
+macro_rules! impl_eq_reversed {
+    ($($t:ty),*) => {
+        $(
+            impl<'a> PartialEq<WCString> for $t {
+                fn eq(&self, other: &WCString) -> bool {
+                    PartialEq::eq(other, self)
+                }
+            }
+        )*
+    }
+}
+
+/// Compare a "wide" string with a string by streaming ```encode_wide```, without allocating.
+///
+/// # ```str``` comparison example
+///
+///     use wcstr::WCString;
+///     let s = WCString::from_str("expected").unwrap();
+///     assert_eq!(s, "expected");
+///     assert_eq!("expected", s);
+///     assert_eq!(*s, String::from("expected"));
+///     assert!(String::from("expected") == s);
+///     assert!(s != "expected!");
+impl PartialEq<str> for WCString {
+    fn eq(&self, other: &str) -> bool {
+        PartialEq::eq(self.as_wcstr(), other)
+    }
+}
+
+impl_eq_str!(&'a str, String, OsStr, &'a OsStr, OsString);
+impl_eq_reversed!(str, &'a str, String, OsStr, &'a OsStr, OsString);
+
+macro_rules! impl_ord_os_str {
+    ($($t:ty),*) => {
+        $(
+            impl<'a> PartialOrd<$t> for WCString {
+                fn partial_cmp(&self, other: &$t) -> Option<std::cmp::Ordering> {
+                    PartialOrd::partial_cmp(self.as_wcstr(), other)
+                }
+            }
+
+            impl<'a> PartialOrd<WCString> for $t {
+                fn partial_cmp(&self, other: &WCString) -> Option<std::cmp::Ordering> {
+                    PartialOrd::partial_cmp(self, other.as_wcstr())
+                }
+            }
+        )*
+    }
+}
+
+impl_ord_os_str!(OsStr, &'a OsStr, OsString);
+
+impl PartialEq<[u16]> for WCString {
+    fn eq(&self, other: &[u16]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl PartialEq<WCString> for [u16] {
+    fn eq(&self, other: &WCString) -> bool {
+        self == other.as_slice()
+    }
+}
+
+impl PartialOrd<[u16]> for WCString {
+    fn partial_cmp(&self, other: &[u16]) -> Option<std::cmp::Ordering> {
+        self.as_slice().partial_cmp(other)
+    }
+}
+
+impl PartialOrd<WCString> for [u16] {
+    fn partial_cmp(&self, other: &WCString) -> Option<std::cmp::Ordering> {
+        self.partial_cmp(other.as_slice())
+    }
+}
+
+impl<'a> PartialEq<&'a [u16]> for WCString {
+    fn eq(&self, other: &&'a [u16]) -> bool {
+        PartialEq::eq(self.as_wcstr(), other)
+    }
+}
+
+impl<'a> PartialEq<WCString> for &'a [u16] {
+    fn eq(&self, other: &WCString) -> bool {
+        PartialEq::eq(self, other.as_wcstr())
+    }
+}
+
+impl<'a> PartialOrd<&'a [u16]> for WCString {
+    fn partial_cmp(&self, other: &&'a [u16]) -> Option<std::cmp::Ordering> {
+        PartialOrd::partial_cmp(self.as_wcstr(), other)
+    }
+}
+
+impl<'a> PartialOrd<WCString> for &'a [u16] {
+    fn partial_cmp(&self, other: &WCString) -> Option<std::cmp::Ordering> {
+        PartialOrd::partial_cmp(self, other.as_wcstr())
+    }
+}
+
+impl<const N: usize> PartialEq<[u16; N]> for WCString {
+    fn eq(&self, other: &[u16; N]) -> bool {
+        PartialEq::eq(self.as_wcstr(), other)
+    }
+}
+
+impl<const N: usize> PartialEq<WCString> for [u16; N] {
+    fn eq(&self, other: &WCString) -> bool {
+        PartialEq::eq(self, other.as_wcstr())
+    }
+}
+
+impl<const N: usize> PartialOrd<[u16; N]> for WCString {
+    fn partial_cmp(&self, other: &[u16; N]) -> Option<std::cmp::Ordering> {
+        PartialOrd::partial_cmp(self.as_wcstr(), other)
+    }
+}
+
+impl<const N: usize> PartialOrd<WCString> for [u16; N] {
+    fn partial_cmp(&self, other: &WCString) -> Option<std::cmp::Ordering> {
+        PartialOrd::partial_cmp(self, other.as_wcstr())
+    }
+}
+
+impl<'a> IntoIterator for &'a WCString {
+    type Item = u16;
+    type IntoIter = CodeUnits<'a>;
+    fn into_iter(self) -> CodeUnits<'a> {
+        self.code_units()
+    }
+}
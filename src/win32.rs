@@ -0,0 +1,277 @@
+
+use ::std;
+use ::std::cmp::Ordering;
+use ::std::io;
+
+use ::{WCStr, WCString, WCBuffer, AsOptPtr, AnsiPolicy};
+
+/// ```LOCALE_NAME_MAX_LENGTH```, including the ```nul``` terminator.
+const LOCALE_NAME_MAX_LENGTH: usize = 85;
+
+#[allow(non_snake_case)]
+#[link(name = "kernel32")]
+extern "system" {
+    fn GetUserDefaultLocaleName(lpLocaleName: *mut u16, cchLocaleName: i32) -> i32;
+    fn GetSystemDefaultLocaleName(lpLocaleName: *mut u16, cchLocaleName: i32) -> i32;
+    fn LCIDToLocaleName(Locale: u32, lpName: *mut u16, cchName: i32, dwFlags: u32) -> i32;
+    fn LocaleNameToLCID(lpName: *const u16, dwFlags: u32) -> u32;
+    fn CompareStringEx(lpLocaleName: *const u16, dwCmpFlags: u32, lpString1: *const u16, cchCount1: i32,
+                       lpString2: *const u16, cchCount2: i32, lpVersionInformation: *mut u8,
+                       lpReserved: *mut u8, lParam: isize) -> i32;
+    fn LCMapStringEx(lpLocaleName: *const u16, dwMapFlags: u32, lpSrcStr: *const u16, cchSrc: i32,
+                     lpDestStr: *mut u16, cchDest: i32, lpVersionInformation: *mut u8, lpReserved: *mut u8,
+                     sortHandle: isize) -> i32;
+    fn WideCharToMultiByte(CodePage: u32, dwFlags: u32, lpWideCharStr: *const u16, cchWideChar: i32,
+                           lpMultiByteStr: *mut u8, cbMultiByte: i32, lpDefaultChar: *const u8,
+                           lpUsedDefaultChar: *mut i32) -> i32;
+    fn MultiByteToWideChar(CodePage: u32, dwFlags: u32, lpMultiByteStr: *const u8, cbMultiByte: i32,
+                           lpWideCharStr: *mut u16, cchWideChar: i32) -> i32;
+    fn ExpandEnvironmentStringsW(lpSrc: *const u16, lpDst: *mut u16, nSize: u32) -> u32;
+}
+
+const LCMAP_LOWERCASE: u32 = 0x0000_0100;
+const LCMAP_UPPERCASE: u32 = 0x0000_0200;
+const LCMAP_TITLECASE: u32 = 0x0000_0300;
+const LCMAP_LINGUISTIC_CASING: u32 = 0x0100_0000;
+
+const CP_UTF8: u32 = 65001;
+const WC_ERR_INVALID_CHARS: u32 = 0x0000_0080;
+const WC_NO_BEST_FIT_CHARS: u32 = 0x0000_0400;
+
+/// Call ```f``` with a buffer of ```capacity``` units. ```f``` returns the number of units written
+/// including the ```nul``` terminator, or 0 on failure (with ```GetLastError``` set).
+fn fill_buffer<F>(capacity: usize, f: F) -> io::Result<WCString>
+    where F: FnOnce(*mut u16, i32) -> i32 {
+    let mut v = vec![0u16; capacity];
+    let n = f(v.as_mut_ptr(), capacity as i32);
+    if n <= 0 {
+        return Err(io::Error::last_os_error());
+    }
+    v.truncate(n as usize - 1);
+    Ok(WCString::from_vec_truncate(v))
+}
+
+impl WCString {
+    /// Decode a string in a code page, such as ```1252``` or ```CP_ACP``` (```0```), using
+    /// ```MultiByteToWideChar```. The bytes are truncated at the first ```nul```, if any, as found in
+    /// fixed-size ```char``` buffers.
+    pub fn from_ansi(code_page: u32, bytes: &[u8]) -> io::Result<WCString> {
+        let bytes = match bytes.iter().position(|&b| b == 0) {
+            Some(i) => &bytes[..i],
+            None => bytes,
+        };
+        if bytes.is_empty() {
+            return Ok(WCString::new());
+        }
+        let convert = |dest: *mut u16, len: i32| unsafe {
+            MultiByteToWideChar(code_page, 0, bytes.as_ptr(), bytes.len() as i32, dest, len)
+        };
+        let required = convert(std::ptr::null_mut(), 0);
+        if required <= 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let mut buffer = WCBuffer::new(required as usize + 1);
+        let written = convert(buffer.as_mut_ptr(), required);
+        if written <= 0 {
+            return Err(io::Error::last_os_error());
+        }
+        unsafe { buffer.set_len(written as usize) };
+        Ok(buffer.into_wcstring())
+    }
+
+    /// Return the user default locale name, such as ```en-US```, using ```GetUserDefaultLocaleName```.
+    ///
+    /// # ```user_default_locale_name()``` example
+    ///     use wcstr::WCString;
+    ///     let name = WCString::user_default_locale_name().unwrap();
+    ///     assert!(!name.is_empty());
+    pub fn user_default_locale_name() -> io::Result<WCString> {
+        fill_buffer(LOCALE_NAME_MAX_LENGTH, |p, n| unsafe { GetUserDefaultLocaleName(p, n) })
+    }
+
+    /// Return the system default locale name using ```GetSystemDefaultLocaleName```.
+    pub fn system_default_locale_name() -> io::Result<WCString> {
+        fill_buffer(LOCALE_NAME_MAX_LENGTH, |p, n| unsafe { GetSystemDefaultLocaleName(p, n) })
+    }
+
+    /// Return the locale name of a locale identifier using ```LCIDToLocaleName```.
+    ///
+    /// # ```from_lcid()``` example
+    ///     use wcstr::WCString;
+    ///     let name = WCString::from_lcid(0x0409).unwrap();
+    ///     assert!(name == "en-US");
+    ///     assert!(name.to_lcid().unwrap() == 0x0409);
+    pub fn from_lcid(lcid: u32) -> io::Result<WCString> {
+        fill_buffer(LOCALE_NAME_MAX_LENGTH, |p, n| unsafe { LCIDToLocaleName(lcid, p, n, 0) })
+    }
+}
+
+impl WCStr {
+    /// Return the locale identifier of this locale name using ```LocaleNameToLCID```.
+    pub fn to_lcid(&self) -> io::Result<u32> {
+        match unsafe { LocaleNameToLCID(self.as_ptr(), 0) } {
+            0 => Err(io::Error::last_os_error()),
+            lcid => Ok(lcid),
+        }
+    }
+
+    /// Compare two "wide" strings with the collation of a locale using ```CompareStringEx```.
+    ///
+    /// * ```locale``` is a locale name such as ```en-US```, or ```None``` for the user default locale.
+    /// * ```flags``` are the ```dwCmpFlags``` of ```CompareStringEx```, such as ```NORM_IGNORECASE```
+    ///   (```0x1```) or ```SORT_DIGITSASNUMBERS``` (```0x8```).
+    ///
+    /// # ```compare_locale()``` example
+    ///     use std::cmp::Ordering;
+    ///     use wcstr::{WCStr, WCString};
+    ///     let a = WCString::from_str("apple").unwrap();
+    ///     let b = WCString::from_str("Banana").unwrap();
+    ///     assert!(a.compare_locale(&b, None::<&WCStr>, 0).unwrap() == Ordering::Less);
+    ///     let en = WCString::from_str("en-US").unwrap();
+    ///     let c = WCString::from_str("APPLE").unwrap();
+    ///     assert!(a.compare_locale(&c, Some(&en), 0x1).unwrap() == Ordering::Equal);
+    pub fn compare_locale<T, L>(&self, other: T, locale: Option<L>, flags: u32) -> io::Result<Ordering>
+        where T: AsRef<WCStr>, L: AsRef<WCStr> {
+        let other = other.as_ref();
+        let result = unsafe {
+            CompareStringEx(locale.as_opt_ptr(), flags, self.as_ptr(), self.len() as i32, other.as_ptr(),
+                            other.len() as i32, std::ptr::null_mut(), std::ptr::null_mut(), 0)
+        };
+        match result {
+            1 => Ok(Ordering::Less),
+            2 => Ok(Ordering::Equal),
+            3 => Ok(Ordering::Greater),
+            _ => Err(io::Error::last_os_error()),
+        }
+    }
+
+    fn lcmap<L>(&self, locale: Option<L>, flags: u32) -> io::Result<WCString>
+        where L: AsRef<WCStr> {
+        if self.is_empty() {
+            return Ok(WCString::new());
+        }
+        let map = |dest: *mut u16, len: i32| unsafe {
+            LCMapStringEx(locale.as_opt_ptr(), flags, self.as_ptr(), self.len() as i32, dest, len,
+                          std::ptr::null_mut(), std::ptr::null_mut(), 0)
+        };
+        let required = map(std::ptr::null_mut(), 0);
+        if required <= 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let mut buffer = WCBuffer::new(required as usize + 1);
+        let written = map(buffer.as_mut_ptr(), required);
+        if written <= 0 {
+            return Err(io::Error::last_os_error());
+        }
+        unsafe { buffer.set_len(written as usize) };
+        Ok(buffer.into_wcstring())
+    }
+
+    /// Convert this "wide" string to uppercase with the linguistic casing rules of a locale, using
+    /// ```LCMapStringEx```. ```locale``` is a locale name such as ```tr-TR```, or ```None``` for the user
+    /// default locale.
+    ///
+    /// # ```to_uppercase_locale()``` example
+    ///     use wcstr::WCString;
+    ///     let s = WCString::from_str("istanbul").unwrap();
+    ///     let tr = WCString::from_str("tr-TR").unwrap();
+    ///     assert!(s.to_uppercase_locale(Some(&tr)).unwrap() == "\u{130}STANBUL");
+    ///     let en = WCString::from_str("en-US").unwrap();
+    ///     assert!(s.to_uppercase_locale(Some(&en)).unwrap() == "ISTANBUL");
+    pub fn to_uppercase_locale<L>(&self, locale: Option<L>) -> io::Result<WCString>
+        where L: AsRef<WCStr> {
+        self.lcmap(locale, LCMAP_UPPERCASE | LCMAP_LINGUISTIC_CASING)
+    }
+
+    /// Convert this "wide" string to lowercase with the linguistic casing rules of a locale, using
+    /// ```LCMapStringEx```.
+    pub fn to_lowercase_locale<L>(&self, locale: Option<L>) -> io::Result<WCString>
+        where L: AsRef<WCStr> {
+        self.lcmap(locale, LCMAP_LOWERCASE | LCMAP_LINGUISTIC_CASING)
+    }
+
+    /// Convert this "wide" string to titlecase for a locale, using ```LCMapStringEx```.
+    pub fn to_titlecase_locale<L>(&self, locale: Option<L>) -> io::Result<WCString>
+        where L: AsRef<WCStr> {
+        self.lcmap(locale, LCMAP_TITLECASE)
+    }
+
+    /// Encode this "wide" string in a code page, such as ```1252``` or ```CP_ACP``` (```0```), using
+    /// ```WideCharToMultiByte```. ```policy``` decides what happens to characters that don't exist in the code
+    /// page. For ```CP_UTF8``` (```65001```) only ```Strict``` makes a difference, rejecting unpaired surrogates.
+    ///
+    /// # ```to_ansi()``` example
+    ///     use wcstr::{AnsiPolicy, WCString};
+    ///     let s = WCString::from_str("Caf\u{E9} \u{20AC}5").unwrap();
+    ///     let bytes = s.to_ansi(1252, AnsiPolicy::Strict).unwrap();
+    ///     assert!(bytes == b"Caf\xE9 \x805");
+    ///     assert!(WCString::from_ansi(1252, &bytes).unwrap() == s);
+    ///     let s = WCString::from_str("\u{100}\u{3A9}").unwrap();
+    ///     assert!(s.to_ansi(1252, AnsiPolicy::Replace(b'_')).unwrap() == b"__");
+    ///     assert!(s.to_ansi(1252, AnsiPolicy::Strict).is_err());
+    ///     assert!(s.to_ansi(1252, AnsiPolicy::BestFit).unwrap()[0] == b'A');
+    pub fn to_ansi(&self, code_page: u32, policy: AnsiPolicy) -> io::Result<Vec<u8>> {
+        if self.is_empty() {
+            return Ok(Vec::new());
+        }
+        let utf8 = code_page == CP_UTF8;
+        let (flags, default_char) = match policy {
+            AnsiPolicy::Strict if utf8 => (WC_ERR_INVALID_CHARS, None),
+            _ if utf8 => (0, None),
+            AnsiPolicy::BestFit => (0, None),
+            AnsiPolicy::Replace(b) => (WC_NO_BEST_FIT_CHARS, Some(b)),
+            AnsiPolicy::Strict => (WC_NO_BEST_FIT_CHARS, None),
+        };
+        let default_char: *const u8 = match default_char {
+            Some(ref b) => b,
+            None => std::ptr::null(),
+        };
+        let mut used_default = 0;
+        let used_default_ptr: *mut i32 = if !utf8 && policy == AnsiPolicy::Strict {
+            &mut used_default
+        }
+        else {
+            std::ptr::null_mut()
+        };
+        let convert = |dest: *mut u8, len: i32| unsafe {
+            WideCharToMultiByte(code_page, flags, self.as_ptr(), self.len() as i32, dest, len, default_char,
+                                used_default_ptr)
+        };
+        let required = convert(std::ptr::null_mut(), 0);
+        if required <= 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let mut v = vec![0u8; required as usize];
+        let written = convert(v.as_mut_ptr(), required);
+        if written <= 0 {
+            return Err(io::Error::last_os_error());
+        }
+        if used_default != 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "character not in code page"));
+        }
+        v.truncate(written as usize);
+        Ok(v)
+    }
+
+    /// Expand ```%NAME%``` environment variable references using ```ExpandEnvironmentStringsW```, such as a
+    /// ```REG_EXPAND_SZ``` value read from the registry. References to undefined variables are left as they are.
+    ///
+    /// # ```expand_env()``` example
+    ///     use std::env;
+    ///     use wcstr::WCString;
+    ///     let s = WCString::from_str("%SystemRoot%\\system32").unwrap();
+    ///     let expected = format!("{}\\system32", env::var("SystemRoot").unwrap());
+    ///     assert!(s.expand_env().unwrap() == *expected);
+    ///     let s = WCString::from_str("%WCSTR_NOT_DEFINED%").unwrap();
+    ///     assert!(s.expand_env().unwrap() == s);
+    pub fn expand_env(&self) -> io::Result<WCString> {
+        WCString::from_win32(|buf, capacity| {
+            // The result counts the nul, and is the required size when the buffer is too small.
+            match unsafe { ExpandEnvironmentStringsW(self.as_ptr(), buf, capacity) } {
+                0 => Err(io::Error::last_os_error()),
+                n if n <= capacity => Ok(n - 1),
+                n => Ok(n),
+            }
+        })
+    }
+}